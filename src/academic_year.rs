@@ -0,0 +1,129 @@
+use std::{fmt, ops::RangeInclusive};
+
+use chrono::{Datelike, NaiveDate};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The month academic years start in, September.
+const FIRST_MONTH: u32 = 9;
+
+/// A UoN academic year, like 2023/24, which runs from the 1st of September
+/// to the 31st of August. Memberships are usually bought for one of these.
+///
+/// With the `serde` feature this is (de)serialized as the year it starts in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct AcademicYear {
+    /// The calendar year the academic year starts in, like 2023 for 2023/24.
+    pub start_year: i32,
+}
+
+impl AcademicYear {
+    pub const fn new(start_year: i32) -> Self {
+        Self { start_year }
+    }
+
+    /// The academic year that `date` is in. Anything from September onwards
+    /// is in the year starting that September, and anything before is in the
+    /// year that started the September before.
+    pub fn containing(date: NaiveDate) -> Self {
+        if date.month() >= FIRST_MONTH {
+            Self::new(date.year())
+        } else {
+            Self::new(date.year() - 1)
+        }
+    }
+
+    /// The first and last days of the academic year, inclusive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the year is so far in the past or future that chrono can't
+    /// represent its dates.
+    pub fn range(&self) -> RangeInclusive<NaiveDate> {
+        let start = NaiveDate::from_ymd_opt(self.start_year, FIRST_MONTH, 1)
+            .expect("academic year is out of range");
+        let end = NaiveDate::from_ymd_opt(self.start_year + 1, FIRST_MONTH, 1)
+            .and_then(|next_start| next_start.pred_opt())
+            .expect("academic year is out of range");
+
+        start..=end
+    }
+
+    /// Whether `date` is in this academic year.
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        Self::containing(date) == *self
+    }
+}
+
+impl fmt::Display for AcademicYear {
+    /// Formats the year like "2023/24".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{:02}",
+            self.start_year,
+            (self.start_year + 1).rem_euclid(100)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::AcademicYear;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_containing_september_boundary() {
+        assert_eq!(
+            AcademicYear::containing(date(2023, 8, 31)),
+            AcademicYear::new(2022)
+        );
+        assert_eq!(
+            AcademicYear::containing(date(2023, 9, 1)),
+            AcademicYear::new(2023)
+        );
+        assert_eq!(
+            AcademicYear::containing(date(2023, 12, 31)),
+            AcademicYear::new(2023)
+        );
+        assert_eq!(
+            AcademicYear::containing(date(2024, 1, 1)),
+            AcademicYear::new(2023)
+        );
+    }
+
+    #[test]
+    fn test_range() {
+        let range = AcademicYear::new(2023).range();
+
+        assert_eq!(*range.start(), date(2023, 9, 1));
+        assert_eq!(*range.end(), date(2024, 8, 31));
+
+        for day in [*range.start(), *range.end()] {
+            assert_eq!(AcademicYear::containing(day), AcademicYear::new(2023));
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let year = AcademicYear::new(2023);
+
+        assert!(year.contains(date(2023, 9, 1)));
+        assert!(year.contains(date(2024, 8, 31)));
+        assert!(!year.contains(date(2023, 8, 31)));
+        assert!(!year.contains(date(2024, 9, 1)));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AcademicYear::new(2023).to_string(), "2023/24");
+        assert_eq!(AcademicYear::new(1999).to_string(), "1999/00");
+        assert_eq!(AcademicYear::new(2009).to_string(), "2009/10");
+    }
+}
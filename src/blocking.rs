@@ -0,0 +1,151 @@
+//! A synchronous wrapper around [`SumsClient`], for scripts that don't want
+//! to set up an async runtime.
+//!
+//! Each [`BlockingSumsClient`] creates its own current-thread tokio runtime
+//! and blocks on it for every call, so it mustn't be used from inside another
+//! async runtime. Use [`SumsClient`] directly there. The WebDriver session is
+//! driven by a task on the runtime that connected it, so the client has to
+//! be created by the wrapper too, with [`BlockingSumsClient::new`] or
+//! [`BlockingSumsClient::from_builder`].
+
+use tokio::runtime::{self, Runtime};
+
+use crate::client::{
+    SumsClient, SumsClientAuthError, SumsClientBuilder, SumsClientError, SumsClientMembersError,
+    SumsClientNewError,
+};
+use crate::member::Member;
+
+/// A [`SumsClient`] that blocks instead of returning futures.
+pub struct BlockingSumsClient {
+    client: SumsClient,
+    runtime: Runtime,
+}
+
+impl BlockingSumsClient {
+    /// See [`SumsClient::new`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the runtime can't be created, or
+    /// if the client fails to connect to the WebDriver server.
+    pub fn new(
+        group_id: u16,
+        webdriver_address: &str,
+        browser_name: &str,
+    ) -> Result<Self, SumsClientNewError> {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        let client =
+            runtime.block_on(SumsClient::new(group_id, webdriver_address, browser_name))?;
+
+        Ok(Self { client, runtime })
+    }
+
+    /// Builds the client from a [`SumsClientBuilder`], for when you need
+    /// more control over how it's configured. See
+    /// [`SumsClientBuilder::build`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the runtime can't be created, or
+    /// if [`SumsClientBuilder::build`] fails.
+    pub fn from_builder(builder: SumsClientBuilder) -> Result<Self, SumsClientNewError> {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        let client = runtime.block_on(builder.build())?;
+
+        Ok(Self { client, runtime })
+    }
+
+    /// See [`SumsClient::authenticate`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if [`SumsClient::authenticate`]
+    /// fails.
+    pub fn authenticate<S>(&self, username: S, password: S) -> Result<(), SumsClientAuthError>
+    where
+        S: AsRef<str>,
+    {
+        self.runtime
+            .block_on(self.client.authenticate(username, password))
+    }
+
+    /// See [`SumsClient::members`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if [`SumsClient::members`] fails.
+    pub fn members(&self) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.runtime.block_on(self.client.members())
+    }
+
+    /// See [`SumsClient::close`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if [`SumsClient::close`] fails.
+    pub fn close(self) -> Result<(), SumsClientError> {
+        self.runtime.block_on(self.client.close())
+    }
+
+    /// The async client this wraps, for calling methods that don't have a
+    /// blocking version yet.
+    pub fn as_async(&self) -> &SumsClient {
+        &self.client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::client::{SumsClient, SumsClientMembersError};
+
+    use super::BlockingSumsClient;
+
+    const GROUP_ID: u16 = 213;
+    const WEBDRIVER_ADDRESS: &str = "http://localhost:9515";
+
+    #[test]
+    fn test_blocking_members() -> Result<(), SumsClientMembersError> {
+        let client = BlockingSumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium").unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .expect("Auth failed");
+
+        client.members()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_blocking_from_builder() -> Result<(), SumsClientMembersError> {
+        let client = BlockingSumsClient::from_builder(
+            SumsClient::builder()
+                .group_id(GROUP_ID)
+                .webdriver_address(WEBDRIVER_ADDRESS),
+        )
+        .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .expect("Auth failed");
+
+        client.members()?;
+
+        Ok(())
+    }
+}
@@ -1,25 +1,138 @@
-use std::num::ParseIntError;
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    future::Future,
+    io::Write,
+    num::ParseIntError,
+    path::Path,
+    pin::Pin,
+    sync::{PoisonError, RwLock},
+    time::Duration,
+};
 
-use chrono::NaiveDate;
+use chrono::{Local, NaiveDate, Utc};
 use fantoccini::{
+    elements::Element,
     error::{CmdError, NewSessionError},
+    key::Key,
     wd::Capabilities,
-    Client, ClientBuilder, Locator,
+    Client, Locator,
 };
+use futures::{pin_mut, Stream, TryStreamExt};
 use once_cell::sync::Lazy;
+use serde_json::Value as Json;
 use thiserror::Error;
+use tokio::{sync::Mutex, time::Instant};
 use url::Url;
 
-use crate::member::{Member, MemberType};
+use crate::academic_year::AcademicYear;
+use crate::event::{Event, EventFilter};
+use crate::export::MemberCsvWriter;
+use crate::group::GroupInfo;
+use crate::member::{
+    count_by_type, with_committee_roles, CommitteeMember, Member, MemberFields, MemberSort,
+    MemberType, StudentId, StudentIdParseError, SubscriptionParseError, UnknownMemberTypeError,
+};
+use crate::product::Product;
+use crate::session::{AuthInfo, SessionCookie, SessionState};
+use crate::transaction::Transaction;
+
+use self::browser::Browser;
+use self::table::{EntriesInfo, MemberColumns};
+
+use self::builder::Connection;
+pub use self::builder::{PageLoadStrategy, SumsClientBuilder, TlsBackend};
+pub use self::pool::SumsClientPool;
+pub use self::selectors::{Selector, Selectors};
 
-/// The base URL of the SUMS website. This is a string instead of a Url since
-/// Fantoccini takes URLs as strings.
-const BASE_URL: &str = "https://su.nottingham.ac.uk";
+mod browser;
+mod builder;
+mod pool;
+mod selectors;
+pub(crate) mod table;
+
+/// The default base URL of the SUMS website, unless overridden with
+/// [`SumsClient::with_base_url`].
+static BASE_URL: Lazy<Url> = Lazy::new(|| Url::parse("https://su.nottingham.ac.uk").unwrap());
 
 /// The source code for the addShowAllEntries() function. See the source code in
 /// the associated file for more information.
-const ADD_SHOW_ALL_ENTRIES_JS: &'static str = include_str!("js/add_show_all_entries.js");
+const ADD_SHOW_ALL_ENTRIES_JS: &str = include_str!("js/add_show_all_entries.js");
+
+/// The source code for downloading a file's contents from inside the page.
+/// See the source code in the associated file for more information.
+const FETCH_TEXT_JS: &str = include_str!("js/fetch_text.js");
+
+/// The environment variable [`SumsClient::authenticate_from_env`] reads the
+/// UoN username from.
+pub const USERNAME_ENV_VAR: &str = "SUMS_USERNAME";
+
+/// The environment variable [`SumsClient::authenticate_from_env`] reads the
+/// UoN password from.
+pub const PASSWORD_ENV_VAR: &str = "SUMS_PASSWORD";
+
+/// The banner SUMS shows across its maintenance and outage pages.
+const MAINTENANCE_BANNER: &str = ".maintenance, .maintenance-banner, #maintenance";
+
+/// Links to group pages on the student dashboard, like the ones in the group
+/// switcher.
+const GROUP_LINKS: &str = "a[href*='/groups/']";
+
+/// The ID of the members table.
+const MEMBERS_TABLE_ID: &str = "group-member-list-datatable";
+
+/// The ID of the committee table.
+const COMMITTEE_TABLE_ID: &str = "group-committee-list-datatable";
+
+/// The ID of the events table.
+const EVENTS_TABLE_ID: &str = "group-event-list-datatable";
+
+/// The ID of the products table.
+const PRODUCTS_TABLE_ID: &str = "group-product-list-datatable";
+
+/// The ID of the table on the sales report page.
+const SALES_TABLE_ID: &str = "group-sales-list-datatable";
+
+/// How many members [`SumsClient::export_members_csv_streaming`] writes
+/// between flushes.
+const CSV_FLUSH_INTERVAL: usize = 100;
 
+/// The export button above the members table, which links to SUMS's own CSV
+/// of the group's members.
+const MEMBERS_EXPORT_LINK: &str = "a[href*='members/export'], a[href*='export'][href$='.csv']";
+
+/// The "Show X entries" select above the members table.
+const PAGE_SIZE_SELECT: &str =
+    "#group-member-list-datatable_length > label:nth-child(1) > select:nth-child(1)";
+
+/// How long to wait for elements to appear before giving up, unless
+/// overridden with [`SumsClient::with_timeout`].
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long the whole login flow can take before giving up, unless overridden
+/// with [`SumsClient::with_auth_timeout`].
+const DEFAULT_AUTH_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How many times to retry flaky WebDriver commands, unless overridden with
+/// [`SumsClient::with_max_retries`].
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// How often to check the state of the page when waiting for something other
+/// than an element appearing, like the members table being redrawn.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often [`SumsClient::wait_for_member`] looks for the member again. Each
+/// look reloads the members table, so this shouldn't be too often.
+const MEMBER_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The delay before the first retry. This doubles with each attempt.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// The longest we'll wait between two retries, before jitter is added.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// The default URL of the student dashboard, unless overridden with
+/// [`SumsClient::with_dashboard_url`].
 static DASHBOARD_URL: Lazy<Url> =
     Lazy::new(|| Url::parse("https://student-dashboard.sums.su").unwrap());
 
@@ -27,12 +140,85 @@ static DASHBOARD_URL: Lazy<Url> =
 pub enum SumsClientError {
     #[error("A WebDriver command failed: {0:?}")]
     WebDriverCmdError(#[from] CmdError),
+
+    #[error("Still logged in after clicking the logout link")]
+    LogoutFailed,
+
+    #[error("An I/O error occured: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Group {0} doesn't exist, or you don't administer it")]
+    GroupInaccessible(u16),
+
+    #[error("The WebDriver is unreachable: {0}")]
+    WebDriverUnreachable(CmdError),
+
+    #[error("Failed to download a file: {0}")]
+    DownloadFailed(String),
+
+    #[error("SUMS is down for maintenance, try again later")]
+    ServiceUnavailable,
+
+    #[error("The script threw an exception: {0}")]
+    ScriptError(String),
+
+    #[error("Not logged in to the student dashboard, ended up at {0}")]
+    NotLoggedIn(String),
+
+    #[error("Failed to {step}: {source}")]
+    NavigationFailed {
+        step: &'static str,
+        source: Box<SumsClientError>,
+        /// The page's HTML when it failed, if
+        /// [`SumsClient::with_capture_on_error`] is on.
+        context: Option<String>,
+    },
+
+    #[error("Failed to parse {step}: {reason}")]
+    ParseFailed {
+        step: &'static str,
+        /// What couldn't be parsed, and why.
+        reason: String,
+        /// The page's HTML when it failed, if
+        /// [`SumsClient::with_capture_on_error`] is on.
+        context: Option<String>,
+    },
+}
+
+impl SumsClientError {
+    /// The step that failed, like "open the events page", for
+    /// [`SumsClientError::NavigationFailed`] and
+    /// [`SumsClientError::ParseFailed`].
+    pub fn step(&self) -> Option<&'static str> {
+        match self {
+            SumsClientError::NavigationFailed { step, .. }
+            | SumsClientError::ParseFailed { step, .. } => Some(step),
+            _ => None,
+        }
+    }
+
+    /// The page's HTML when a navigation or parse failed, for working out
+    /// what SUMS changed. This is only captured if
+    /// [`SumsClient::with_capture_on_error`] is on.
+    pub fn context(&self) -> Option<&str> {
+        match self {
+            SumsClientError::NavigationFailed { context, .. }
+            | SumsClientError::ParseFailed { context, .. } => context.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Error)]
 pub enum SumsClientNewError {
     #[error("Failed to create new WebDriver session: {0:?}")]
     WebDriverNewSessionError(#[from] NewSessionError),
+
+    #[error("Required field {0} wasn't set on the builder")]
+    MissingField(&'static str),
+
+    #[error("Failed to create the async runtime: {0}")]
+    Runtime(#[from] std::io::Error),
 }
 
 #[derive(Debug, Error)]
@@ -42,6 +228,18 @@ pub enum SumsClientAuthError {
 
     #[error("Authentication failed with message {0}")]
     AuthFailedError(String),
+
+    #[error("Ended up on an unexpected page while authenticating: {0}")]
+    UnexpectedPage(String),
+
+    #[error("UoN SSO asked for two-factor authentication")]
+    MfaRequired,
+
+    #[error("The {0} environment variable isn't set, or isn't valid unicode")]
+    MissingCredential(&'static str),
+
+    #[error("Authentication didn't finish within {0:?}")]
+    Timeout(Duration),
 }
 
 impl From<CmdError> for SumsClientAuthError {
@@ -53,13 +251,142 @@ impl From<CmdError> for SumsClientAuthError {
 #[derive(Debug, Error)]
 pub enum SumsClientMembersError {
     #[error("A generic error occured: {0:?}")]
-    SumsClientError(#[from] SumsClientError),
+    SumsClientError(SumsClientError),
+
+    #[error("Group {0} doesn't exist, or you can't see its members")]
+    GroupInaccessible(u16),
+
+    #[error("Failed to {step}: {source}")]
+    NavigationFailed {
+        step: &'static str,
+        source: SumsClientError,
+        /// The page's HTML when it failed, if
+        /// [`SumsClient::with_capture_on_error`] is on.
+        context: Option<String>,
+    },
+
+    #[error("Failed to parse {step}: {source}")]
+    ParseFailed {
+        step: &'static str,
+        source: Box<SumsClientMembersError>,
+        /// The page's HTML when it failed, if
+        /// [`SumsClient::with_capture_on_error`] is on.
+        context: Option<String>,
+    },
 
     #[error("Failed to convert string to integer. Usually means invalid student ID.")]
     ParseIntError(#[from] ParseIntError),
 
-    #[error("Failed to parse date joined.")]
-    ChronoParseError(#[from] chrono::ParseError),
+    #[error("Failed to parse date {value:?}: {source}")]
+    ChronoParseError {
+        source: chrono::ParseError,
+        /// The text that couldn't be parsed as a date.
+        value: String,
+    },
+
+    #[error("Invalid student ID {0:?} in members table.")]
+    InvalidStudentId(String),
+
+    #[error("Unknown member type {0:?} in members table.")]
+    UnknownMemberType(String),
+
+    #[error("Failed to parse subscription {0:?} in members table.")]
+    InvalidSubscription(String),
+
+    #[error("Expected {expected} columns in table row, found {found}. The table layout has probably changed.")]
+    UnexpectedTableLayout { expected: usize, found: usize },
+
+    #[error("The members table doesn't offer a page size of {0}")]
+    UnsupportedPageSize(usize),
+
+    #[error("Failed to parse the members table's entry count from {0:?}")]
+    UnexpectedEntriesInfo(String),
+
+    #[error("Failed to parse price {0:?}.")]
+    InvalidPrice(String),
+
+    #[error("Failed to parse tickets sold {0:?} in events table.")]
+    InvalidTickets(String),
+
+    #[error("Unknown product availability {0:?} in products table.")]
+    UnknownProductAvailability(String),
+
+    #[error("The table's page size select doesn't have any usable options")]
+    NoPageSizeOptions,
+
+    #[error("The injected JavaScript threw an exception: {0}")]
+    ScriptError(String),
+
+    #[error("Failed to read CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Gave up waiting after {0:?}")]
+    Timeout(Duration),
+
+    #[error("The members table says there are {expected} members, but {found} were read")]
+    MemberCountMismatch { expected: usize, found: usize },
+}
+
+impl SumsClientMembersError {
+    /// Whether getting to the page with the data failed. These are often
+    /// worth retrying.
+    pub fn is_navigation_failure(&self) -> bool {
+        matches!(self, SumsClientMembersError::NavigationFailed { .. })
+    }
+
+    /// Whether the data on the page couldn't be understood. Retrying these
+    /// won't help, since it usually means SUMS's layout has changed.
+    pub fn is_parse_failure(&self) -> bool {
+        matches!(
+            self,
+            SumsClientMembersError::ParseFailed { .. }
+                | SumsClientMembersError::ParseIntError(_)
+                | SumsClientMembersError::ChronoParseError { .. }
+                | SumsClientMembersError::InvalidStudentId(_)
+                | SumsClientMembersError::UnknownMemberType(_)
+                | SumsClientMembersError::InvalidSubscription(_)
+                | SumsClientMembersError::UnexpectedTableLayout { .. }
+                | SumsClientMembersError::UnexpectedEntriesInfo(_)
+                | SumsClientMembersError::InvalidPrice(_)
+                | SumsClientMembersError::InvalidTickets(_)
+                | SumsClientMembersError::UnknownProductAvailability(_)
+                | SumsClientMembersError::NoPageSizeOptions
+                | SumsClientMembersError::Csv(_)
+        )
+    }
+
+    /// The step that failed, like "open the members table", for
+    /// [`SumsClientMembersError::NavigationFailed`] and
+    /// [`SumsClientMembersError::ParseFailed`].
+    pub fn step(&self) -> Option<&'static str> {
+        match self {
+            SumsClientMembersError::NavigationFailed { step, .. }
+            | SumsClientMembersError::ParseFailed { step, .. } => Some(step),
+            _ => None,
+        }
+    }
+
+    /// The page's HTML when a navigation or parse failed, for working out
+    /// what SUMS changed. This is only captured if
+    /// [`SumsClient::with_capture_on_error`] is on.
+    pub fn context(&self) -> Option<&str> {
+        match self {
+            SumsClientMembersError::NavigationFailed { context, .. }
+            | SumsClientMembersError::ParseFailed { context, .. } => context.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl From<SumsClientError> for SumsClientMembersError {
+    fn from(err: SumsClientError) -> Self {
+        match err {
+            SumsClientError::GroupInaccessible(group_id) => {
+                SumsClientMembersError::GroupInaccessible(group_id)
+            }
+            err => SumsClientMembersError::SumsClientError(err),
+        }
+    }
 }
 
 impl From<CmdError> for SumsClientMembersError {
@@ -68,19 +395,68 @@ impl From<CmdError> for SumsClientMembersError {
     }
 }
 
+impl From<StudentIdParseError> for SumsClientMembersError {
+    fn from(err: StudentIdParseError) -> Self {
+        SumsClientMembersError::InvalidStudentId(err.0)
+    }
+}
+
+impl From<SubscriptionParseError> for SumsClientMembersError {
+    fn from(err: SubscriptionParseError) -> Self {
+        SumsClientMembersError::InvalidSubscription(err.0)
+    }
+}
+
+impl From<UnknownMemberTypeError> for SumsClientMembersError {
+    fn from(err: UnknownMemberTypeError) -> Self {
+        SumsClientMembersError::UnknownMemberType(err.0)
+    }
+}
+
 pub struct SumsClient {
-    client: Client,
+    /// Behind a lock so that [`SumsClient::reconnect`] can swap in a new
+    /// session.
+    session: RwLock<Client>,
+    /// How the client was connected, if it was built by
+    /// [`SumsClientBuilder`], so that it can connect again.
+    connection: Option<Connection>,
+    auto_reconnect: bool,
+    /// The username and password from the last successful
+    /// [`SumsClient::authenticate`], kept for logging in again after
+    /// reconnecting. These are only kept with auto reconnect on.
+    credentials: std::sync::Mutex<Option<(String, String)>>,
+    /// Held while reconnecting, so only one reconnect happens at a time.
+    reconnecting: Mutex<()>,
     group_id: u16,
+    timeout: Duration,
+    auth_timeout: Duration,
+    max_retries: u32,
+    base_url: Url,
+    dashboard_url: Url,
+    login_url: Option<Url>,
+    min_interval: Duration,
+    date_format: Option<String>,
+    reuse_dashboard: bool,
+    capture_on_error: bool,
+    stay_signed_in: bool,
+    strict_count: bool,
+    selectors: Selectors,
+    /// When the last navigation started, for enforcing `min_interval`.
+    last_navigation: Mutex<Option<Instant>>,
 }
 
 impl SumsClient {
     /// Creates a new SumsClient instance
     ///
     /// - `group_id` - The ID of your group. You can find it by going to your group's management
-    /// page, and looking at the URL.
+    ///   page, and looking at the URL.
     /// - `webdriver_address` - The address of your webdriver server.
     /// - `browser_name` The name of the browser you're using, as defined by the WebDriver spec.
-    /// For example, "chrome"
+    ///   For example, "chrome"
+    ///
+    /// Chromium is recommended, since geckodriver can't run multiple sessions
+    /// simultaneously. If you need anything more than the browser name, use
+    /// [`SumsClient::with_capabilities`].
     ///
     /// # Errors
     ///
@@ -90,192 +466,3657 @@ impl SumsClient {
         webdriver_address: &str,
         browser_name: &str,
     ) -> Result<Self, SumsClientNewError> {
-        let mut client_builder = ClientBuilder::rustls();
-
         let mut capabilities = Capabilities::new();
         capabilities.insert("browserName".to_string(), browser_name.into());
-        client_builder.capabilities(capabilities);
 
-        let client = client_builder.connect(webdriver_address.as_ref()).await?;
-
-        Ok(Self { client, group_id })
+        Self::with_capabilities(group_id, webdriver_address, capabilities).await
     }
 
-    pub async fn authenticate<S>(&self, username: S, password: S) -> Result<(), SumsClientAuthError>
+    /// Creates a new SumsClient instance using the given WebDriver capabilities
+    /// verbatim. This is useful for things like running Chrome headless, or
+    /// connecting to a Selenium grid with its own requirements.
+    ///
+    /// - `group_id` - The ID of your group. You can find it by going to your group's management
+    ///   page, and looking at the URL.
+    /// - `webdriver_address` - The address of your webdriver server.
+    /// - `capabilities` - The capabilities to request when creating the session. These must
+    ///   include `browserName` if your WebDriver server requires it.
+    ///
+    /// As with [`SumsClient::new`], chromium is recommended since geckodriver
+    /// can't run multiple sessions simultaneously.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the client fails to connect.
+    pub async fn with_capabilities<S>(
+        group_id: u16,
+        webdriver_address: S,
+        capabilities: Capabilities,
+    ) -> Result<Self, SumsClientNewError>
     where
         S: AsRef<str>,
     {
-        self.client.goto(BASE_URL).await?;
+        SumsClientBuilder::new()
+            .group_id(group_id)
+            .webdriver_address(webdriver_address.as_ref())
+            .capabilities(capabilities)
+            .build()
+            .await
+    }
 
-        // Click on the user icon in the top right
-        self.client
-            .find(Locator::Id("userActionsInvoker"))
-            .await?
-            .click()
-            .await?;
+    /// Creates a new SumsClient instance running headless chromium, which is
+    /// what you want in most CI setups.
+    ///
+    /// This passes `--headless=new`, `--disable-gpu` and `--no-sandbox` to
+    /// Chrome. `--no-sandbox` is needed in a lot of containerised CI
+    /// environments, where Chrome's sandbox can't be set up, but it does mean
+    /// the browser is less isolated from the host.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the client fails to connect.
+    pub async fn new_headless<S>(
+        group_id: u16,
+        webdriver_address: S,
+    ) -> Result<Self, SumsClientNewError>
+    where
+        S: AsRef<str>,
+    {
+        Self::with_capabilities(
+            group_id,
+            webdriver_address,
+            builder::headless_capabilities(),
+        )
+        .await
+    }
 
-        // Click on the student login button
-        self.client
-            .find(Locator::XPath("//*[@id=\"userActions\"]/ul/li[1]/a[1]"))
-            .await?
-            .click()
-            .await?;
+    /// Creates a SumsClient from an already connected fantoccini client, for
+    /// when you're sharing one browser session with other automation.
+    ///
+    /// The caller owns the session's lifecycle: the WebDriver session won't be
+    /// closed unless you call [`SumsClient::close`], and anything else
+    /// driving the same browser may interfere with this client's navigation.
+    pub fn from_client(client: Client, group_id: u16) -> Self {
+        Self {
+            session: RwLock::new(client),
+            connection: None,
+            group_id,
+            timeout: DEFAULT_TIMEOUT,
+            auth_timeout: DEFAULT_AUTH_TIMEOUT,
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_url: BASE_URL.clone(),
+            dashboard_url: DASHBOARD_URL.clone(),
+            login_url: None,
+            min_interval: Duration::ZERO,
+            date_format: None,
+            reuse_dashboard: false,
+            capture_on_error: false,
+            stay_signed_in: false,
+            strict_count: false,
+            auto_reconnect: false,
+            credentials: std::sync::Mutex::new(None),
+            reconnecting: Mutex::new(()),
+            selectors: Selectors::default(),
+            last_navigation: Mutex::new(None),
+        }
+    }
 
-        // Find the UoN login form
-        let login_form = self
-            .client
-            .form(Locator::XPath("/html/body/div/div/div/div[1]/form"))
-            .await?;
+    /// Creates a [`SumsClientBuilder`], for when you need more control over
+    /// how the client is configured.
+    pub fn builder() -> SumsClientBuilder {
+        SumsClientBuilder::new()
+    }
 
-        // Fill in the username/password
-        login_form
-            .set(Locator::Id("username"), username.as_ref())
-            .await?;
-        login_form
-            .set(Locator::Id("password"), password.as_ref())
-            .await?;
+    /// The ID of the group that methods like [`SumsClient::members`] act on.
+    pub fn group_id(&self) -> u16 {
+        self.group_id
+    }
 
-        login_form.submit().await?;
+    /// Switches which group methods like [`SumsClient::members`] act on,
+    /// without having to log in again.
+    pub fn set_group(&mut self, group_id: u16) {
+        self.group_id = group_id;
+    }
 
-        // Try to look for an error message on the login screen
-        let login_error = self
-            .client
-            .find(Locator::XPath("/html/body/div/div/div/div[1]/section/p"))
-            .await;
+    /// Gets the underlying fantoccini client, so you can drive the browser
+    /// yourself for pages this crate doesn't support yet.
+    ///
+    /// Using this is unsupported. This crate assumes it's the only thing
+    /// navigating the browser, so anything you do with the raw client (like
+    /// leaving pages open, logging out, or switching windows or frames) may
+    /// break later calls on this client.
+    ///
+    /// This is the session the client is using now. With
+    /// [`SumsClient::with_auto_reconnect`] on, the session can be replaced
+    /// at any time, so get the raw client again rather than holding onto it.
+    pub fn raw_client(&self) -> Client {
+        self.client()
+    }
 
-        // If an error message was found, we're still on the login screen, so
-        // auth failed. Otherwise, we are on the SU screen and auth succeeded.
-        match login_error {
-            Ok(element) => Err(SumsClientAuthError::AuthFailedError(element.text().await?)),
-            Err(_) => Ok(()),
-        }
+    /// Sets how long to wait for elements to appear on the page before giving
+    /// up. Defaults to 30 seconds.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
     }
 
-    pub async fn members(&self) -> Result<Vec<Member>, SumsClientMembersError> {
-        self.go_to_member_page().await?;
+    /// Sets how long logging in can take altogether before giving up, so a
+    /// scheduled job doesn't hang forever when UoN SSO is slow. This includes
+    /// waiting for the one-time code in [`SumsClient::authenticate_with_mfa`].
+    /// Defaults to 60 seconds.
+    pub fn with_auth_timeout(mut self, auth_timeout: Duration) -> Self {
+        self.auth_timeout = auth_timeout;
+        self
+    }
 
-        self.client
-            .goto(&format!(
-                "https://student-dashboard.sums.su/groups/{}/members",
-                self.group_id
-            ))
-            .await?;
+    /// Sets how many times flaky WebDriver commands (like navigating or
+    /// finding elements while SUMS re-renders the page) are retried before
+    /// giving up. Defaults to 3. Setting this to 0 disables retrying.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
 
-        self.client
-            .execute(ADD_SHOW_ALL_ENTRIES_JS, Vec::new())
-            .await?;
+    /// Sets the URL of the SU website, which is where authentication starts.
+    /// Defaults to `https://su.nottingham.ac.uk`. This is mostly useful for
+    /// testing against a mirror or staging copy of the site.
+    pub fn with_base_url(mut self, base_url: Url) -> Self {
+        self.base_url = base_url;
+        self
+    }
 
-        // let entry_count_u64 = entry_count.as_u64().unwrap_or(100000);
-        let entry_count_u64 = 100000;
+    /// Sets the URL of the student dashboard, which is where group pages live.
+    /// Defaults to `https://student-dashboard.sums.su`.
+    pub fn with_dashboard_url(mut self, dashboard_url: Url) -> Self {
+        self.dashboard_url = dashboard_url;
+        self
+    }
 
-        let entry_count_selector = self
-            .client
-            .find(Locator::Css(
-                "#group-member-list-datatable_length > label:nth-child(1) > select:nth-child(1)",
-            ))
-            .await?;
+    /// Sets a URL to start logging in from, instead of clicking the student
+    /// login button on the SU website. This is for logging in through a
+    /// different identity provider than UoN SSO, like a staging
+    /// environment's. If its login page doesn't look like UoN's, use
+    /// [`SumsClient::with_selectors`] as well.
+    ///
+    /// The login still has to end up back on the SU website (see
+    /// [`SumsClient::with_base_url`]) for it to count as done.
+    pub fn with_login_url(mut self, login_url: Url) -> Self {
+        self.login_url = Some(login_url);
+        self
+    }
 
-        entry_count_selector
-            .select_by_value(&entry_count_u64.to_string())
-            .await?;
+    /// Sets the shortest time to leave between navigating to one page and the
+    /// next, so scraping lots of groups in a loop doesn't hammer SUMS. Clicks
+    /// within a page aren't throttled. Defaults to zero, i.e. no throttling.
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
 
-        let table_body = self
-            .client
-            .find(Locator::Css(
-                "#group-member-list-datatable > tbody:nth-child(2)",
-            ))
-            .await?;
+    /// Sets the format of dates in the members, events and sales tables, in
+    /// chrono's `strftime` syntax (e.g. `"%d/%m/%Y"`), for when the dashboard's locale settings
+    /// make it show dates differently. This format is tried first, and if a
+    /// date doesn't match it, the formats SUMS is known to use are tried too.
+    /// By default, only the known formats are tried, starting with
+    /// `"%Y-%m-%d"`.
+    pub fn with_date_format<S: Into<String>>(mut self, date_format: S) -> Self {
+        self.date_format = Some(date_format.into());
+        self
+    }
 
-        let member_elements = table_body.find_all(Locator::Css("tr")).await?;
+    /// Sets whether to skip going through the SU website to get to the
+    /// student dashboard when the browser is already on the dashboard, which
+    /// saves a couple of page loads for back to back calls like
+    /// [`SumsClient::members`]. Defaults to false, so every call starts from
+    /// the SU website, which logs the dashboard in again in case its session
+    /// expired while sitting on one of its pages.
+    pub fn with_reuse_dashboard(mut self, reuse_dashboard: bool) -> Self {
+        self.reuse_dashboard = reuse_dashboard;
+        self
+    }
 
-        let mut members = Vec::new();
+    /// Sets whether to save the page's HTML when getting to or parsing a page
+    /// fails, as the error's [`context`](SumsClientMembersError::context).
+    /// This makes it much easier to work out what changed when SUMS's layout
+    /// breaks something, but fetching the page source is slow and the errors
+    /// get big, so it's off by default.
+    pub fn with_capture_on_error(mut self, capture_on_error: bool) -> Self {
+        self.capture_on_error = capture_on_error;
+        self
+    }
 
-        for member_element in member_elements {
-            let member_table_data = member_element.find_all(Locator::Css("td")).await?;
+    /// Sets how to answer the "Stay signed in?" page UoN SSO sometimes shows
+    /// after the password. Defaults to false, answering "No", so that the
+    /// login doesn't outlive the browser session.
+    pub fn with_stay_signed_in(mut self, stay_signed_in: bool) -> Self {
+        self.stay_signed_in = stay_signed_in;
+        self
+    }
 
-            let member = Member::new(
-                member_table_data[0].text().await?,
-                member_table_data[1].text().await?,
-                MemberType::Student,
-                member_table_data[3].text().await?,
-                NaiveDate::parse_from_str(&member_table_data[4].text().await?, "%Y-%m-%d")?,
-            );
+    /// Sets whether reading a different number of members than the members
+    /// table says it has is an error. After scraping, the count is always
+    /// checked against the table's "of Z entries" total, since a mismatch
+    /// usually means rows were missed. Defaults to false, which only logs a
+    /// warning; with true the scrape fails with
+    /// [`SumsClientMembersError::MemberCountMismatch`] instead.
+    pub fn with_strict_count(mut self, strict_count: bool) -> Self {
+        self.strict_count = strict_count;
+        self
+    }
 
-            members.push(member);
+    /// Sets whether to start a new WebDriver session when the current one
+    /// dies, like when the browser crashes, instead of every later call
+    /// failing. Defaults to false.
+    ///
+    /// Whenever a WebDriver command fails because the session's gone, a new
+    /// one is connected with the same address and capabilities, then logged
+    /// in again with the credentials from the last successful
+    /// [`SumsClient::authenticate`], and the command is run once more. To make that possible, turning this on
+    /// keeps the username and password in memory for as long as the client
+    /// lives. Logins with [`SumsClient::authenticate_with_mfa`] aren't kept,
+    /// since they can't be repeated unattended. Whatever call was running
+    /// when the session died may still fail, but the ones after it won't.
+    ///
+    /// This only works for clients made with [`SumsClientBuilder`] (or the
+    /// constructors that use it), since a client from
+    /// [`SumsClient::from_client`] doesn't know how to connect.
+    pub fn with_auto_reconnect(mut self, auto_reconnect: bool) -> Self {
+        if auto_reconnect && self.connection.is_none() {
+            warn!("auto reconnect needs a client made with SumsClientBuilder, ignoring it");
         }
 
-        Ok(members)
+        self.auto_reconnect = auto_reconnect;
+        self
     }
 
-    async fn go_to_member_page(&self) -> Result<(), SumsClientError> {
-        self.client.goto(BASE_URL).await?;
+    /// Sets the selectors used to find things on the page while logging in
+    /// and on the members table. SUMS and UoN login can change their markup at any time, so this lets
+    /// you patch a selector that's stopped working without waiting for a new
+    /// release. See [`Selectors`].
+    pub fn with_selectors(mut self, selectors: Selectors) -> Self {
+        self.selectors = selectors;
+        self
+    }
 
-        let user_button = self.client.find(Locator::Id("userActionsInvoker")).await?;
-        user_button.click().await?;
+    /// Logs into SUMS through the SU website with a UoN username and password.
+    ///
+    /// If the session is already logged in, this does nothing and succeeds,
+    /// so it's safe to call again, e.g. when retrying.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the login page reports that the
+    /// credentials were wrong, if UoN SSO asks for two-factor authentication,
+    /// if we end up somewhere other than the login page or the SU website
+    /// within the client's timeout, if logging in takes longer than the
+    /// client's auth timeout, or if a WebDriver command fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn authenticate<S>(&self, username: S, password: S) -> Result<(), SumsClientAuthError>
+    where
+        S: AsRef<str>,
+    {
+        self.login(username.as_ref(), password.as_ref(), None::<fn() -> String>)
+            .await
+    }
 
-        let login_button = self
-            .client
-            .find(Locator::Id("studentDashboardLink"))
-            .await?;
-        login_button.click().await?;
+    /// Like [`SumsClient::authenticate`], but if UoN SSO asks for a one-time
+    /// code, `mfa_code` is called to get one (e.g. from a TOTP generator) and
+    /// it's typed in.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the login page reports that the
+    /// credentials were wrong, if the two-factor prompt doesn't accept a
+    /// typed code (like a Duo push), if we end up somewhere other than the
+    /// login page or the SU website within the client's timeout, if logging
+    /// in takes longer than the client's auth timeout, or if a WebDriver
+    /// command fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn authenticate_with_mfa<S, F>(
+        &self,
+        username: S,
+        password: S,
+        mfa_code: F,
+    ) -> Result<(), SumsClientAuthError>
+    where
+        S: AsRef<str>,
+        F: FnOnce() -> String,
+    {
+        self.login(username.as_ref(), password.as_ref(), Some(mfa_code))
+            .await
+    }
 
-        self.client.wait().for_url(DASHBOARD_URL.clone()).await?;
+    /// Like [`SumsClient::authenticate`], but reads the username and password
+    /// from the `SUMS_USERNAME` and `SUMS_PASSWORD` environment variables
+    /// (see [`USERNAME_ENV_VAR`] and [`PASSWORD_ENV_VAR`]), so that tools
+    /// built on this crate all look for credentials in the same place.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if either variable isn't set or
+    /// isn't valid unicode, or if [`SumsClient::authenticate`] fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn authenticate_from_env(&self) -> Result<(), SumsClientAuthError> {
+        let username = credential_from_env(USERNAME_ENV_VAR)?;
+        let password = credential_from_env(PASSWORD_ENV_VAR)?;
 
-        Ok(())
+        self.authenticate(username, password).await
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::env;
+    /// Like [`SumsClient::authenticate`], but also reads the name of the
+    /// account that was logged in from the SU website's user menu. Not being
+    /// able to find the name isn't an error, it's just left as `None`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if [`SumsClient::authenticate`]
+    /// fails, or if a WebDriver command fails.
+    pub async fn authenticate_detailed<S>(
+        &self,
+        username: S,
+        password: S,
+    ) -> Result<AuthInfo, SumsClientAuthError>
+    where
+        S: AsRef<str>,
+    {
+        self.authenticate(username, password).await?;
 
-    use crate::client::{SumsClient, SumsClientAuthError, SumsClientNewError};
+        // authenticate leaves us on the SU website, so the user menu is there
+        self.ensure_user_menu_open().await?;
 
-    use super::SumsClientMembersError;
+        let display_name = match self
+            .try_find(self.selectors.user_display_name.locator())
+            .await?
+        {
+            Some(element) => Some(element.text().await?.trim().to_string()),
+            None => None,
+        }
+        .filter(|name| !name.is_empty());
 
-    const GROUP_ID: u16 = 213;
-    const WEBDRIVER_ADDRESS: &str = "http://localhost:9515";
+        if display_name.is_none() {
+            warn!("couldn't find the logged in user's name");
+        }
 
-    #[tokio::test]
-    async fn test_create_client() -> Result<(), SumsClientNewError> {
-        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium").await?;
+        Ok(AuthInfo::new(display_name))
+    }
 
-        assert_eq!(GROUP_ID, client.group_id);
+    /// Logs out of SUMS, leaving the browser session in an anonymous state.
+    ///
+    /// If the session isn't logged in, this does nothing and succeeds.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// the session is still logged in after clicking the logout link.
+    pub async fn logout(&self) -> Result<(), SumsClientError> {
+        let Some(logout_link) = self.find_logout_link().await? else {
+            return Ok(());
+        };
 
-        Ok(())
+        logout_link.click().await?;
+
+        // Make sure the logout actually took effect
+        match self.find_logout_link().await? {
+            Some(_) => Err(SumsClientError::LogoutFailed),
+            None => Ok(()),
+        }
     }
 
-    #[tokio::test]
-    async fn test_auth() -> Result<(), SumsClientAuthError> {
-        // test_create_client should handle this
-        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+    /// Checks that the WebDriver is still up and responding, so tools can fail
+    /// fast with a clear message at startup rather than deep inside
+    /// [`SumsClient::authenticate`].
+    ///
+    /// This doesn't check that the WebDriver is ready for a new session, since
+    /// some (like geckodriver) say they aren't while this client's is open.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`SumsClientError::WebDriverUnreachable`] if
+    /// the WebDriver's status can't be fetched.
+    pub async fn ping(&self) -> Result<(), SumsClientError> {
+        // Not through SumsClient::command, since reconnecting would hide
+        // exactly what this is checking for
+        let status = self
+            .client()
+            .status()
             .await
-            .unwrap();
-
-        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
-        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+            .map_err(SumsClientError::WebDriverUnreachable)?;
 
-        client.authenticate(username, password).await?;
+        debug!("WebDriver status: {}", status.message);
 
         Ok(())
     }
 
-    #[tokio::test]
-    async fn test_members() -> Result<(), SumsClientMembersError> {
-        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
-            .await
-            .unwrap();
+    /// Checks whether the session is logged in, by looking for the logout
+    /// link on the SU website.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails.
+    pub async fn is_authenticated(&self) -> Result<bool, SumsClientError> {
+        Ok(self.find_logout_link().await?.is_some())
+    }
 
-        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
-        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+    /// Exports the session's cookies for the SU website and the student
+    /// dashboard, so that a later run can pick up where this one left off
+    /// with [`SumsClient::restore_session`]. This visits both sites.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails.
+    pub async fn export_session(&self) -> Result<SessionState, SumsClientError> {
+        let cookies = self.session_cookies().await?;
 
-        client
-            .authenticate(username, password)
-            .await
-            .expect("Auth failed");
+        debug!("exported {} cookies", cookies.len());
 
-        client.members().await?;
+        Ok(SessionState::new(cookies, Utc::now()))
+    }
 
-        Ok(())
+    /// Gets the browser's cookies for the SU website and the student
+    /// dashboard, for handing a logged in session over to a lighter HTTP
+    /// client. Some dashboard pages can be fetched directly with these,
+    /// without driving the browser any further. This visits both sites, but
+    /// leaves the session logged in.
+    ///
+    /// Each cookie keeps its domain, so only send the ones that match the
+    /// host you're requesting. With the `serde` feature they can be
+    /// serialized, like the [`SessionState`] from
+    /// [`SumsClient::export_session`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails.
+    pub async fn session_cookies(&self) -> Result<Vec<SessionCookie>, SumsClientError> {
+        let mut cookies: Vec<SessionCookie> = Vec::new();
+
+        for url in [&self.base_url, &self.dashboard_url] {
+            self.goto(url.as_str()).await?;
+
+            for cookie in self
+                .command(|client| async move { client.get_all_cookies().await })
+                .await?
+            {
+                let cookie = SessionCookie::from(&cookie);
+
+                // Cookies for a parent domain show up on both sites
+                if !cookies.iter().any(|existing| {
+                    existing.name == cookie.name
+                        && existing.domain == cookie.domain
+                        && existing.path == cookie.path
+                }) {
+                    cookies.push(cookie);
+                }
+            }
+        }
+
+        Ok(cookies)
+    }
+
+    /// Adds the cookies from an exported session to the browser, and returns
+    /// whether that left the session logged in. Restored sessions can go
+    /// stale, so if this returns `false` you'll still need to
+    /// [`SumsClient::authenticate`]. [`SumsClient::authenticate_or_restore`]
+    /// does that for you.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails.
+    pub async fn restore_session(&self, state: &SessionState) -> Result<bool, SumsClientError> {
+        for url in [&self.base_url, &self.dashboard_url] {
+            let Some(host) = url.host_str() else {
+                continue;
+            };
+
+            // WebDriver only lets us set cookies for the current page's domain
+            self.goto(url.as_str()).await?;
+
+            for cookie in state
+                .cookies
+                .iter()
+                .filter(|cookie| cookie.matches_host(host))
+            {
+                self.command(|client| async move { client.add_cookie(cookie.to_cookie()).await })
+                    .await?;
+            }
+        }
+
+        self.is_authenticated().await
+    }
+
+    /// Restores an exported session, falling back to logging in with a
+    /// username and password if the session has gone stale.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the session is stale and
+    /// [`SumsClient::authenticate`] fails, or if a WebDriver command fails.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn authenticate_or_restore<S>(
+        &self,
+        state: &SessionState,
+        username: S,
+        password: S,
+    ) -> Result<(), SumsClientAuthError>
+    where
+        S: AsRef<str>,
+    {
+        if self.restore_session(state).await? {
+            debug!("restored session exported at {}", state.exported_at);
+
+            return Ok(());
+        }
+
+        debug!("restored session was stale, logging in again");
+
+        self.authenticate(username, password).await
+    }
+
+    /// Closes the WebDriver session, consuming the client.
+    ///
+    /// You should call this when you're done with a client. Closing a session
+    /// is async, so it can't be done when the client is dropped, and dropped
+    /// clients leave their session open on the WebDriver server until it
+    /// times out. On a Selenium grid, these abandoned sessions can use up all
+    /// of the grid's capacity.
+    ///
+    /// If you can't await this yourself, for example because the client is
+    /// owned by something being torn down, you can close it in the background
+    /// on a best-effort basis:
+    ///
+    /// ```no_run
+    /// # fn example(client: libsums::client::SumsClient) {
+    /// tokio::spawn(async move {
+    ///     let _ = client.close().await;
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the WebDriver server fails to
+    /// close the session.
+    pub async fn close(self) -> Result<(), SumsClientError> {
+        self.client().close().await?;
+
+        Ok(())
+    }
+
+    /// Gets the name of the group, as shown on its dashboard page.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the logged in user doesn't
+    /// administer the group, or if a WebDriver command fails.
+    pub async fn group_info(&self) -> Result<GroupInfo, SumsClientError> {
+        self.go_to_member_page().await?;
+
+        self.goto_group_page(self.group_id, "").await?;
+
+        let name = self.wait_for(Locator::Css("h1")).await?.text().await?;
+
+        Ok(GroupInfo::new(self.group_id, name.trim().to_string()))
+    }
+
+    /// Lists the groups the logged in user administers, from the links to
+    /// them on the student dashboard. This is handy for finding group IDs, or
+    /// letting someone pick which group to use.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails.
+    pub async fn administered_groups(&self) -> Result<Vec<GroupInfo>, SumsClientError> {
+        self.go_to_member_page().await?;
+
+        let mut groups: Vec<GroupInfo> = Vec::new();
+
+        for link in self
+            .command(|client| async move { client.find_all(Locator::Css(GROUP_LINKS)).await })
+            .await?
+        {
+            let Some(group_id) = link
+                .prop("href")
+                .await?
+                .and_then(|href| Url::parse(&href).ok())
+                .filter(|url| url.host_str() == self.dashboard_url.host_str())
+                .and_then(|url| group_id_from_path(url.path()))
+            else {
+                continue;
+            };
+
+            let name = link.text().await?.trim().to_string();
+
+            // Groups are often linked more than once, like from the switcher
+            // and an icon, and not every link has the name in it
+            match groups.iter_mut().find(|group| group.id == group_id) {
+                Some(group) if group.name.is_empty() => group.name = name,
+                Some(_) => {}
+                None => groups.push(GroupInfo::new(group_id, name)),
+            }
+        }
+
+        debug!("found {} administered groups", groups.len());
+
+        Ok(groups)
+    }
+
+    /// Gets the total number of members in the group, without scraping the
+    /// members themselves. This is much faster than calling
+    /// [`SumsClient::members`] and taking the length.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// the entry count shown under the members table can't be parsed.
+    pub async fn member_count(&self) -> Result<usize, SumsClientMembersError> {
+        self.open_members_table(self.group_id).await?;
+
+        Ok(self.entries_info().await?.member_count())
+    }
+
+    /// Gets every member of the group.
+    ///
+    /// SUMS occasionally shows the same member twice, so rows with a student
+    /// ID that's already been seen are skipped, keeping the first. The same
+    /// goes for [`SumsClient::members_for`] and [`SumsClient::members_stream`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(group_id = self.group_id)))]
+    pub async fn members(&self) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.members_for(self.group_id).await
+    }
+
+    /// Like [`SumsClient::members`], but for a different group than the one
+    /// the client was created with. This lets one authenticated session fetch
+    /// members for every group you administer.
+    pub async fn members_for(&self, group_id: u16) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.members_stream_for(group_id, MemberFields::ALL)
+            .try_collect()
+            .await
+    }
+
+    /// Like [`SumsClient::members`], but only reads the details in `fields`
+    /// (and the student ID), leaving the rest empty. Each column that isn't
+    /// read saves a WebDriver round trip per member, which adds up for big
+    /// groups. See [`MemberFields`] for what each field costs.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn members_with_fields(
+        &self,
+        fields: MemberFields,
+    ) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.members_stream_for(self.group_id, fields)
+            .try_collect()
+            .await
+    }
+
+    /// Like [`SumsClient::members`], but calls `progress` after each member
+    /// is read, for driving a progress bar. It's given how many members have
+    /// been read so far, and how many there are in total if the members
+    /// table says.
+    ///
+    /// ```no_run
+    /// # async fn example(client: libsums::client::SumsClient) -> Result<(), libsums::client::SumsClientMembersError> {
+    /// let members = client
+    ///     .members_with_progress(|done, total| match total {
+    ///         Some(total) => eprint!("\r{}/{} members", done, total),
+    ///         None => eprint!("\r{} members", done),
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn members_with_progress<F>(
+        &self,
+        mut progress: F,
+    ) -> Result<Vec<Member>, SumsClientMembersError>
+    where
+        F: FnMut(usize, Option<usize>),
+    {
+        let stream = self.members_stream_for(self.group_id, MemberFields::ALL);
+        pin_mut!(stream);
+
+        let mut members = Vec::new();
+        let mut total = None;
+
+        while let Some(member) = stream.try_next().await? {
+            // The members table is showing by the time the first member
+            // arrives. Not knowing the total only makes the progress less
+            // useful, so carry on without it
+            if members.is_empty() {
+                total = match self.entries_info().await {
+                    Ok(entries_info) => Some(entries_info.member_count()),
+                    Err(err) => {
+                        debug!("couldn't read the number of members: {}", err);
+                        None
+                    }
+                };
+            }
+
+            members.push(member);
+            progress(members.len(), total);
+        }
+
+        Ok(members)
+    }
+
+    /// Like [`SumsClient::members`], but collects the members into a map keyed
+    /// by student ID, for looking members up.
+    ///
+    /// Unlike [`SumsClient::members`], if SUMS shows the same student ID
+    /// twice the last row wins, and a warning is logged.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn members_by_id(
+        &self,
+    ) -> Result<HashMap<StudentId, Member>, SumsClientMembersError> {
+        self.open_members_table(self.group_id).await?;
+
+        self.show_all_entries().await?;
+
+        let mut members = HashMap::new();
+
+        for member in self.read_member_rows().await? {
+            if let Some(previous) = members.insert(member.student_id.clone(), member) {
+                warn!("replacing duplicate row for {}", previous.student_id);
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// Like [`SumsClient::members`], but yields each member as soon as their
+    /// row has been parsed, rather than collecting them all first. This is
+    /// useful for very large groups.
+    ///
+    /// ```no_run
+    /// # async fn example(client: libsums::client::SumsClient) -> Result<(), libsums::client::SumsClientMembersError> {
+    /// use futures::{pin_mut, StreamExt};
+    ///
+    /// let members = client.members_stream();
+    /// pin_mut!(members);
+    ///
+    /// while let Some(member) = members.next().await {
+    ///     println!("{}", member?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn members_stream(
+        &self,
+    ) -> impl Stream<Item = Result<Member, SumsClientMembersError>> + '_ {
+        self.members_stream_for(self.group_id, MemberFields::ALL)
+    }
+
+    fn members_stream_for(
+        &self,
+        group_id: u16,
+        fields: MemberFields,
+    ) -> impl Stream<Item = Result<Member, SumsClientMembersError>> + '_ {
+        browser::scrape_members(
+            self,
+            group_id,
+            fields,
+            self.date_format.as_deref(),
+            self.strict_count,
+        )
+        .or_else(move |err| async move { Err(self.capture_page_source(err).await) })
+    }
+
+    /// Gets the raw HTML of the members table, for attaching to bug reports
+    /// when SUMS changes its markup and scraping breaks.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails.
+    pub async fn members_table_html(&self) -> Result<String, SumsClientError> {
+        self.go_to_members_table(self.group_id).await?;
+
+        Ok(self
+            .wait_for(Locator::Id(MEMBERS_TABLE_ID))
+            .await?
+            .html(false)
+            .await?)
+    }
+
+    /// Gets the text of each header of the members table, in the order SUMS
+    /// shows them, with whitespace tidied up. This is for spotting when SUMS
+    /// adds, removes or renames a column before it breaks anything.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails.
+    pub async fn member_columns(&self) -> Result<Vec<String>, SumsClientError> {
+        self.go_to_members_table(self.group_id).await?;
+
+        Ok(self.table_headers(MEMBERS_TABLE_ID).await?)
+    }
+
+    /// Downloads the CSV of the group's members that SUMS offers through the
+    /// export button on the members page. This comes straight from SUMS, so
+    /// it doesn't depend on how the members table is rendered.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the members page doesn't have an
+    /// export button, if the download fails, or if a WebDriver command fails.
+    pub async fn export_members_csv(&self) -> Result<String, SumsClientError> {
+        self.go_to_members_table(self.group_id).await?;
+
+        let export_url = self
+            .wait_for(Locator::Css(MEMBERS_EXPORT_LINK))
+            .await?
+            .prop("href")
+            .await?
+            .ok_or_else(|| {
+                SumsClientError::DownloadFailed("the export button has no link".to_string())
+            })?;
+
+        debug!("downloading the members export from {}", export_url);
+
+        let export_url = &export_url;
+
+        match self
+            .command(|client| async move {
+                client
+                    .execute_async(FETCH_TEXT_JS, vec![export_url.as_str().into()])
+                    .await
+            })
+            .await?
+        {
+            Json::String(csv) => Ok(csv),
+            response => Err(SumsClientError::DownloadFailed(
+                response
+                    .get("error")
+                    .and_then(Json::as_str)
+                    .unwrap_or("the page didn't return the file")
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Gets every member of the group from the CSV downloaded by
+    /// [`SumsClient::export_members_csv`], rather than by scraping the
+    /// members table. Each record is parsed with [`Member::from_csv_record`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the CSV can't be downloaded, or
+    /// if a record can't be parsed.
+    pub async fn export_members(&self) -> Result<Vec<Member>, SumsClientMembersError> {
+        let csv = self.export_members_csv().await?;
+
+        self.with_page_source(
+            table::parse_members_csv(&csv).map_err(parse_failed("the members export")),
+        )
+        .await
+    }
+
+    /// Scrapes the group's members into `writer` as CSV, in the format of
+    /// [`to_csv`](crate::export::to_csv), writing each member as soon as it's
+    /// read from the members table rather than collecting them first.
+    ///
+    /// This is different to [`SumsClient::export_members_csv`], which
+    /// downloads SUMS's own export. The output is flushed every
+    /// 100 members, and before an error is returned, so
+    /// the members read before something went wrong are kept. Duplicate rows
+    /// are skipped like in [`SumsClient::members_stream`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the members table can't be
+    /// scraped, or if writing to `writer` fails. Anything written before then
+    /// is left in `writer`.
+    pub async fn export_members_csv_streaming<W: Write>(
+        &self,
+        writer: W,
+    ) -> Result<(), SumsClientMembersError> {
+        let mut csv_writer = MemberCsvWriter::new(writer)?;
+
+        let members = self.members_stream();
+        pin_mut!(members);
+
+        let mut written = 0;
+
+        loop {
+            let member = match members.try_next().await {
+                Ok(Some(member)) => member,
+                Ok(None) => break,
+                Err(err) => {
+                    // Keep the partial output, the scrape error matters more
+                    // than a flush failing too
+                    if let Err(flush_err) = csv_writer.flush() {
+                        warn!("couldn't flush the partial CSV: {}", flush_err);
+                    }
+
+                    return Err(err);
+                }
+            };
+
+            csv_writer.write(&member)?;
+            written += 1;
+
+            if written % CSV_FLUSH_INTERVAL == 0 {
+                csv_writer.flush()?;
+            }
+        }
+
+        csv_writer.flush()?;
+
+        debug!("wrote {} members to CSV", written);
+
+        Ok(())
+    }
+
+    /// Takes a screenshot of the current page, as PNG bytes. This is meant
+    /// for working out what the browser was showing when something failed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails.
+    pub async fn screenshot(&self) -> Result<Vec<u8>, SumsClientError> {
+        Ok(self
+            .command(|client| async move { client.screenshot().await })
+            .await?)
+    }
+
+    /// Takes a screenshot of the current page with [`SumsClient::screenshot`]
+    /// and saves it as a PNG file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// the file can't be written.
+    pub async fn screenshot_to_file<P>(&self, path: P) -> Result<(), SumsClientError>
+    where
+        P: AsRef<Path>,
+    {
+        let png = self.screenshot().await?;
+        tokio::fs::write(path, png).await?;
+
+        Ok(())
+    }
+
+    /// Runs some JavaScript on the current page, like dismissing a banner
+    /// that gets in the way of scraping. This works like a function body:
+    /// `args` are available as `arguments`, and whatever it `return`s is
+    /// returned, or `null` if it doesn't return anything.
+    ///
+    /// The script runs with the logged in user's access to SUMS, so it can do
+    /// anything they can, including reading member details and changing the
+    /// group. Only run scripts you trust, and never build one out of text
+    /// from somewhere else, like a page or a request; pass that in `args`
+    /// instead.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`SumsClientError::ScriptError`] if the
+    /// script throws, or an error if the WebDriver command fails.
+    pub async fn execute_script(&self, js: &str, args: Vec<Json>) -> Result<Json, SumsClientError> {
+        let args = &args;
+
+        self.command(|client| async move { client.execute(js, args.clone()).await })
+            .await
+            .map_err(|err| match err {
+                CmdError::Standard(ref err) if err.error() == "javascript error" => {
+                    SumsClientError::ScriptError(err.message.to_string())
+                }
+                err => err.into(),
+            })
+    }
+
+    /// Navigates to a page of the student dashboard that this crate doesn't
+    /// have a method for yet, like `groups/213/finance`. If the dashboard
+    /// isn't logged in but the SU website is, this goes through the SU
+    /// website to log it in and tries again. `path` is relative to the
+    /// dashboard, and can have a query string. From there,
+    /// [`SumsClient::execute_script`] can read whatever's on the page.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`SumsClientError::NotLoggedIn`] if the
+    /// dashboard still redirects somewhere else, which means the session
+    /// isn't logged in, or an error if SUMS is down or a WebDriver command
+    /// fails.
+    pub async fn goto_dashboard_path(&self, path: &str) -> Result<(), SumsClientError> {
+        let page_url = self.dashboard_page(path);
+
+        self.goto(&page_url).await?;
+        self.check_service_available().await?;
+
+        // Logged out sessions get sent to the SU website or UoN SSO
+        let mut current_url = self.current_url().await?;
+
+        if is_dashboard_url(&current_url, &self.dashboard_url) {
+            return Ok(());
+        }
+
+        // The dashboard's own session may just have expired, which going
+        // through the SU website fixes as long as that's still logged in.
+        // Checking first avoids waiting for a dashboard link that won't
+        // show up when it isn't
+        if self.find_logout_link().await?.is_some() {
+            debug!("dashboard isn't logged in, going through the SU website");
+
+            self.go_to_member_page().await?;
+
+            self.goto(&page_url).await?;
+            self.check_service_available().await?;
+
+            current_url = self.current_url().await?;
+
+            if is_dashboard_url(&current_url, &self.dashboard_url) {
+                return Ok(());
+            }
+        }
+
+        warn!("tried to open {}, ended up at {}", path, current_url);
+
+        Err(SumsClientError::NotLoggedIn(current_url.to_string()))
+    }
+
+    /// Gets the members who joined during an academic year, using
+    /// [`SumsClient::members_joined_between`] with the year's
+    /// [`range`](AcademicYear::range). The members are returned newest
+    /// first.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn members_in_year(
+        &self,
+        year: AcademicYear,
+    ) -> Result<Vec<Member>, SumsClientMembersError> {
+        let range = year.range();
+
+        self.members_joined_between(*range.start(), *range.end())
+            .await
+    }
+
+    /// Gets the members who joined between `start` and `end`, inclusive.
+    ///
+    /// This sorts the members table by date joined (newest first) so that it
+    /// can stop reading rows as soon as it gets to members who joined before
+    /// `start`, which is much faster than scraping everyone for recent dates.
+    /// If the table can't be sorted, every member is read and filtered
+    /// instead. The members are returned newest first.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn members_joined_between(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.open_members_table(self.group_id).await?;
+
+        self.show_all_entries().await?;
+
+        let date_joined_column = self.member_column_indices().await?.date_joined;
+        let sorted = self.sort_members_table(date_joined_column, false).await?;
+
+        let rows = self.member_rows();
+        pin_mut!(rows);
+
+        let mut members = Vec::new();
+
+        while let Some(member) = rows.try_next().await? {
+            if sorted && member.date_joined < start {
+                break;
+            }
+
+            if member.joined_between(start, end) {
+                members.push(member);
+            }
+        }
+
+        if !sorted {
+            members.sort_by_key(|member| Reverse(member.date_joined));
+        }
+
+        Ok(members)
+    }
+
+    /// Gets every member of the group, sorted by `sort`.
+    ///
+    /// This sorts the members table itself by clicking on the column's header,
+    /// so the members come out in the same order SUMS shows them. If the
+    /// table can't be sorted, the members are sorted after they're read
+    /// instead.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn members_sorted(
+        &self,
+        sort: MemberSort,
+        ascending: bool,
+    ) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.open_members_table(self.group_id).await?;
+
+        self.show_all_entries().await?;
+
+        let columns = self.member_column_indices().await?;
+        let column = match sort {
+            MemberSort::ByStudentId => columns.student_id,
+            MemberSort::ByName => columns.name,
+            MemberSort::ByDateJoined => columns.date_joined,
+        };
+
+        let sorted = self.sort_members_table(column, ascending).await?;
+        let mut members = self.read_member_rows().await?;
+
+        if !sorted {
+            sort.sort(&mut members, ascending);
+        }
+
+        Ok(members)
+    }
+
+    /// Searches for members using the members table's search box, which
+    /// matches any column (so partial names, student IDs, etc. all work).
+    /// Returns an empty list if nothing matches.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a matching row can't be parsed.
+    pub async fn search_members(&self, query: &str) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.open_members_table(self.group_id).await?;
+
+        self.show_all_entries().await?;
+
+        if self.search_members_table(query).await?.total == 0 {
+            return Ok(Vec::new());
+        }
+
+        self.read_member_rows().await
+    }
+
+    /// Gets the group's committee, from its committee page on the student
+    /// dashboard. Vacant positions are skipped.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn committee(&self) -> Result<Vec<CommitteeMember>, SumsClientMembersError> {
+        self.open_group_page("committee", "open the committee page")
+            .await?;
+
+        let rows = browser::table_cells(self, COMMITTEE_TABLE_ID).await?;
+
+        let mut committee = Vec::new();
+
+        for cells in rows {
+            if let Some(committee_member) = self
+                .with_page_source(
+                    table::parse_committee_row(&cells)
+                        .map_err(parse_failed("a committee table row")),
+                )
+                .await?
+            {
+                committee.push(committee_member);
+            }
+        }
+
+        Ok(committee)
+    }
+
+    /// Counts the group's members of each [`MemberType`], like for "X
+    /// students, Y associates" in a committee report. Types nobody has are
+    /// left out, so a group with no members gives an empty map.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn member_type_counts(
+        &self,
+    ) -> Result<HashMap<MemberType, usize>, SumsClientMembersError> {
+        Ok(count_by_type(&self.members().await?))
+    }
+
+    /// Gets every member of the group along with their committee role, if
+    /// they have one. This fetches both the members and the committee, and
+    /// matches them up with [`with_committee_roles`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row of either table can't be parsed.
+    pub async fn members_with_roles(
+        &self,
+    ) -> Result<Vec<(Member, Option<String>)>, SumsClientMembersError> {
+        let members = self.members().await?;
+        let committee = self.committee().await?;
+
+        Ok(with_committee_roles(members, &committee))
+    }
+
+    /// Gets the group's events from its events page on the student dashboard,
+    /// keeping the ones that pass `filter`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn events(&self, filter: EventFilter) -> Result<Vec<Event>, SumsClientError> {
+        self.scrape_events(filter)
+            .await
+            .map_err(page_error("the events page"))
+    }
+
+    /// The scraping behind [`SumsClient::events`], which shares its helpers
+    /// with the members table.
+    async fn scrape_events(
+        &self,
+        filter: EventFilter,
+    ) -> Result<Vec<Event>, SumsClientMembersError> {
+        self.open_group_page("events", "open the events page")
+            .await?;
+        self.show_all_table_entries(EVENTS_TABLE_ID).await?;
+
+        let rows = browser::table_cells(self, EVENTS_TABLE_ID).await?;
+
+        let today = Local::now().date_naive();
+        let mut events = Vec::new();
+
+        for cells in rows {
+            let event = self
+                .with_page_source(
+                    table::parse_event_row(&cells, self.date_format.as_deref())
+                        .map_err(parse_failed("an events table row")),
+                )
+                .await?;
+
+            if filter.matches(event.date, today) {
+                events.push(event);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Gets the products the group sells through the SUMS shop, like
+    /// memberships and merch, from its products page on the student
+    /// dashboard.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn products(&self) -> Result<Vec<Product>, SumsClientError> {
+        self.scrape_products()
+            .await
+            .map_err(page_error("the products page"))
+    }
+
+    /// The scraping behind [`SumsClient::products`].
+    async fn scrape_products(&self) -> Result<Vec<Product>, SumsClientMembersError> {
+        self.open_group_page("products", "open the products page")
+            .await?;
+        self.show_all_table_entries(PRODUCTS_TABLE_ID).await?;
+
+        let products = browser::table_cells(self, PRODUCTS_TABLE_ID)
+            .await?
+            .iter()
+            .map(|cells| {
+                table::parse_product_row(cells).map_err(parse_failed("a products table row"))
+            })
+            .collect();
+
+        self.with_page_source(products).await
+    }
+
+    /// Gets the group's sales between `from` and `to`, inclusive, from its
+    /// sales report on the student dashboard. This is only available to
+    /// accounts that can see the group's finances.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// a row can't be parsed.
+    pub async fn transactions(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Transaction>, SumsClientError> {
+        self.scrape_transactions(from, to)
+            .await
+            .map_err(page_error("the sales report"))
+    }
+
+    /// The scraping behind [`SumsClient::transactions`].
+    async fn scrape_transactions(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Transaction>, SumsClientMembersError> {
+        self.open_group_page("sales", "open the sales report")
+            .await?;
+        self.show_all_table_entries(SALES_TABLE_ID).await?;
+
+        let next_button_id = format!("{}_next", SALES_TABLE_ID);
+        let mut transactions = Vec::new();
+
+        loop {
+            for cells in browser::table_cells(self, SALES_TABLE_ID).await? {
+                let transaction = self
+                    .with_page_source(
+                        table::parse_transaction_row(&cells, self.date_format.as_deref())
+                            .map_err(parse_failed("a sales report row")),
+                    )
+                    .await?;
+
+                if (from..=to).contains(&transaction.date) {
+                    transactions.push(transaction);
+                }
+            }
+
+            // Showing every entry should fit them all on one page, but a
+            // busy group's report might still be paginated if the script
+            // couldn't add a big enough page size
+            let Some(next_button) = self.try_find(Locator::Id(&next_button_id)).await? else {
+                break;
+            };
+
+            let is_last_page = next_button
+                .attr("class")
+                .await?
+                .is_some_and(|class| class.split_whitespace().any(|c| c == "disabled"));
+            if is_last_page {
+                break;
+            }
+
+            let previous_info_text = self.table_info_text(SALES_TABLE_ID).await?;
+            next_button.click().await?;
+            self.wait_for_table_redraw(SALES_TABLE_ID, &previous_info_text)
+                .await?;
+        }
+
+        debug!(
+            "found {} transactions between {} and {}",
+            transactions.len(),
+            from,
+            to
+        );
+
+        Ok(transactions)
+    }
+
+    /// Gets a single page of members, using the members table's own
+    /// pagination rather than loading every member at once. This is gentler
+    /// on the browser for very large groups.
+    ///
+    /// - `page` - The page to get, starting from 0.
+    /// - `page_size` - How many members are on each page. This must be one of
+    ///   the sizes offered by the table's "Show X entries" selector, which are
+    ///   usually 10, 25, 50 and 100.
+    ///
+    /// Pages past the end are returned as an empty list, so to get every page
+    /// either keep going until you get one with fewer than `page_size`
+    /// members, or stop once `page * page_size` reaches
+    /// [`SumsClient::member_count`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if `page_size` isn't offered by the
+    /// table, if a WebDriver command fails, or if a row can't be parsed.
+    pub async fn members_page(
+        &self,
+        page: usize,
+        page_size: usize,
+    ) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.open_members_table(self.group_id).await?;
+
+        let entry_count_selector = self.wait_for(Locator::Css(PAGE_SIZE_SELECT)).await?;
+
+        let page_size_option = format!("option[value=\"{}\"]", page_size);
+        match entry_count_selector
+            .find(Locator::Css(&page_size_option))
+            .await
+        {
+            Ok(_) => {}
+            Err(CmdError::NoSuchElement(_)) => {
+                return Err(SumsClientMembersError::UnsupportedPageSize(page_size))
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        // Only wait for a redraw if the page size actually changes, otherwise
+        // we'd always wait for the full timeout.
+        let page_size = page_size.to_string();
+        let mut entries_info =
+            if entry_count_selector.prop("value").await?.as_ref() == Some(&page_size) {
+                self.entries_info().await?
+            } else {
+                let previous_info_text = self.entries_info_text().await?;
+                entry_count_selector.select_by_value(&page_size).await?;
+                self.wait_for_redraw(&previous_info_text).await?
+            };
+
+        for _ in 0..page {
+            let next_button = self
+                .wait_for(self.selectors.members_next_page.locator())
+                .await?;
+
+            let is_last_page = next_button
+                .attr("class")
+                .await?
+                .is_some_and(|class| class.split_whitespace().any(|c| c == "disabled"));
+            if is_last_page {
+                return Ok(Vec::new());
+            }
+
+            let previous_info_text = self.entries_info_text().await?;
+            next_button.click().await?;
+            entries_info = self.wait_for_redraw(&previous_info_text).await?;
+        }
+
+        if entries_info.total == 0 {
+            return Ok(Vec::new());
+        }
+
+        self.read_member_rows().await
+    }
+
+    /// Looks up a single member by their student ID, using the members
+    /// table's search box rather than scraping every member. Returns `None` if
+    /// nobody in the group has that student ID.
+    ///
+    /// If more than one row somehow has the given student ID, the first one
+    /// is returned.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// the matching row can't be parsed.
+    pub async fn member_by_id(
+        &self,
+        student_id: &str,
+    ) -> Result<Option<Member>, SumsClientMembersError> {
+        self.open_members_table(self.group_id).await?;
+
+        if self.search_members_table(student_id).await?.total == 0 {
+            return Ok(None);
+        }
+
+        // The search box matches any column, so make sure we only return an
+        // exact student ID match.
+        Ok(self
+            .read_member_rows()
+            .await?
+            .into_iter()
+            .find(|member| member.student_id == student_id))
+    }
+
+    /// Whether the student with the given ID is a current paid member of the
+    /// group, looked up with [`SumsClient::member_by_id`] rather than by
+    /// scraping every member.
+    ///
+    /// A current member is one who is in the members table and whose
+    /// subscription is active today, see [`Member::is_subscription_active`].
+    /// Someone in the table who never bought a subscription, or whose
+    /// subscription has run out, isn't a member.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a WebDriver command fails, or if
+    /// the matching row can't be parsed.
+    pub async fn is_member(&self, student_id: &str) -> Result<bool, SumsClientMembersError> {
+        let today = Local::now().date_naive();
+
+        Ok(self
+            .member_by_id(student_id)
+            .await?
+            .is_some_and(|member| member.is_subscription_active(today)))
+    }
+
+    /// Waits for a member with the given student ID to join the group, by
+    /// looking them up with [`SumsClient::member_by_id`] every few seconds.
+    /// This is meant for end to end tests of buying a membership.
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`SumsClientMembersError::Timeout`] if the
+    /// member hasn't shown up within `timeout`, or an error if looking them
+    /// up fails.
+    pub async fn wait_for_member(
+        &self,
+        student_id: &str,
+        timeout: Duration,
+    ) -> Result<Member, SumsClientMembersError> {
+        let poll = async {
+            loop {
+                if let Some(member) = self.member_by_id(student_id).await? {
+                    return Ok::<_, SumsClientMembersError>(member);
+                }
+
+                debug!(
+                    "{} isn't a member yet, looking again in {:?}",
+                    student_id, MEMBER_POLL_INTERVAL
+                );
+                tokio::time::sleep(MEMBER_POLL_INTERVAL).await;
+            }
+        };
+
+        tokio::time::timeout(timeout, poll)
+            .await
+            .map_err(|_| SumsClientMembersError::Timeout(timeout))?
+    }
+
+    /// The login flow behind [`SumsClient::authenticate`] and
+    /// [`SumsClient::authenticate_with_mfa`], giving up after the client's
+    /// auth timeout.
+    async fn login<F>(
+        &self,
+        username: &str,
+        password: &str,
+        mfa_code: Option<F>,
+    ) -> Result<(), SumsClientAuthError>
+    where
+        F: FnOnce() -> String,
+    {
+        // A code from a callback can't be asked for again after reconnecting
+        let remember = self.auto_reconnect && mfa_code.is_none();
+
+        tokio::time::timeout(
+            self.auth_timeout,
+            self.login_steps(username, password, mfa_code),
+        )
+        .await
+        .map_err(|_| SumsClientAuthError::Timeout(self.auth_timeout))??;
+
+        if remember {
+            *self
+                .credentials
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) =
+                Some((username.to_string(), password.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// The current WebDriver session.
+    fn client(&self) -> Client {
+        self.session
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replaces a dead WebDriver session with a new one and logs it in again,
+    /// if [`SumsClient::with_auto_reconnect`] is on. Returns whether there's
+    /// a new session to retry with.
+    ///
+    /// This is boxed because logging in navigates, which can reconnect.
+    fn reconnect(&self) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        Box::pin(async move {
+            let Some(connection) = self.connection.as_ref().filter(|_| self.auto_reconnect) else {
+                return false;
+            };
+
+            // Either another call is already reconnecting, or we're logging in
+            // after reconnecting and the new session died too
+            let Ok(_reconnecting) = self.reconnecting.try_lock() else {
+                return false;
+            };
+
+            warn!("the WebDriver session was lost, reconnecting");
+
+            match connection.connect().await {
+                Ok(client) => {
+                    *self.session.write().unwrap_or_else(PoisonError::into_inner) = client;
+                }
+                Err(err) => {
+                    error!("failed to reconnect to the WebDriver: {}", err);
+                    return false;
+                }
+            }
+
+            let credentials = self
+                .credentials
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone();
+
+            // Still worth retrying without logging in, the page might not
+            // need it
+            if let Some((username, password)) = credentials {
+                if let Err(err) = self
+                    .login(&username, &password, None::<fn() -> String>)
+                    .await
+                {
+                    error!("failed to log in again after reconnecting: {}", err);
+                }
+            }
+
+            true
+        })
+    }
+
+    async fn login_steps<F>(
+        &self,
+        username: &str,
+        password: &str,
+        mut mfa_code: Option<F>,
+    ) -> Result<(), SumsClientAuthError>
+    where
+        F: FnOnce() -> String,
+    {
+        let mut mfa_submitted = false;
+        let mut stay_signed_in_answered = false;
+        let started = Instant::now();
+
+        // This opens the user menu in the top right, which has either the
+        // logout link or the student login button
+        if self.find_logout_link().await?.is_some() {
+            debug!("already logged in, skipping authentication");
+            return Ok(());
+        }
+
+        match &self.login_url {
+            Some(login_url) => {
+                debug!("logging in through {}", login_url);
+                self.goto(login_url.as_str()).await?;
+            }
+            // Click on the student login button
+            None => {
+                self.wait_for(self.selectors.login_link.locator())
+                    .await?
+                    .click()
+                    .await?;
+            }
+        }
+
+        // Find the UoN login form
+        let login_form_locator = self.selectors.login_form.locator();
+        self.wait_for(login_form_locator).await?;
+        let login_form = self
+            .command(|client| async move { client.form(login_form_locator).await })
+            .await?;
+
+        // Fill in the username/password
+        login_form
+            .set(self.selectors.username_input.locator(), username)
+            .await?;
+        login_form
+            .set(self.selectors.password_input.locator(), password)
+            .await?;
+
+        login_form.submit().await?;
+
+        // Wait until we either see an error message on the login screen, or
+        // get sent back to the SU site logged in. If neither happens, the
+        // login flow has probably changed, so don't guess which it was.
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            if let Some(element) = self.try_find(self.selectors.login_error.locator()).await? {
+                let message = element.text().await?;
+                warn!("authentication failed: {}", message);
+
+                return Err(SumsClientAuthError::AuthFailedError(message));
+            }
+
+            // Whether this shows up depends on the state of the SSO session,
+            // so it can come before or after MFA, or not at all
+            if !stay_signed_in_answered
+                && self
+                    .try_find(self.selectors.stay_signed_in_prompt.locator())
+                    .await?
+                    .is_some()
+            {
+                let button = if self.stay_signed_in {
+                    &self.selectors.stay_signed_in_yes
+                } else {
+                    &self.selectors.stay_signed_in_no
+                };
+
+                if let Some(button) = self.try_find(button.locator()).await? {
+                    debug!(
+                        "answering {} to staying signed in",
+                        if self.stay_signed_in { "yes" } else { "no" }
+                    );
+
+                    button.click().await?;
+                    stay_signed_in_answered = true;
+                }
+            }
+
+            if !mfa_submitted
+                && self
+                    .try_find(self.selectors.mfa_prompt.locator())
+                    .await?
+                    .is_some()
+            {
+                let code_input = self
+                    .try_find(self.selectors.mfa_code_input.locator())
+                    .await?;
+
+                match (mfa_code.take(), code_input) {
+                    (Some(mfa_code), Some(code_input)) => {
+                        debug!("entering a one-time code for two-factor authentication");
+
+                        code_input
+                            .send_keys(&format!("{}{}", mfa_code(), char::from(Key::Enter)))
+                            .await?;
+                        mfa_submitted = true;
+                    }
+                    _ => {
+                        warn!("authentication needs two-factor authentication");
+
+                        return Err(SumsClientAuthError::MfaRequired);
+                    }
+                }
+            }
+
+            let current_url = self.current_url().await?;
+
+            if current_url.host_str() == self.base_url.host_str()
+                && self
+                    .try_find(self.selectors.user_menu.locator())
+                    .await?
+                    .is_some()
+            {
+                debug!("authenticated in {:?}", started.elapsed());
+
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                error!(
+                    "timed out waiting for the result of authentication, ended up at {}",
+                    current_url
+                );
+
+                return Err(SumsClientAuthError::UnexpectedPage(current_url.to_string()));
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Makes the members table show every member on one page, rather than
+    /// hiding them behind pagination.
+    async fn show_all_entries(&self) -> Result<(), SumsClientMembersError> {
+        self.show_all_table_entries(MEMBERS_TABLE_ID).await
+    }
+
+    /// Sorts the members table by one of its columns, by clicking on the
+    /// column's header until DataTables reports it's sorted the right way.
+    /// Returns whether the table ended up sorted.
+    async fn sort_members_table(&self, column: usize, ascending: bool) -> Result<bool, CmdError> {
+        let aria_sort = if ascending { "ascending" } else { "descending" };
+
+        let header = self
+            .wait_for(Locator::Css(&format!(
+                "#group-member-list-datatable > thead th:nth-child({})",
+                column + 1
+            )))
+            .await?;
+
+        // DataTables cycles through ascending and descending as the header is
+        // clicked, so this should never take more than two clicks.
+        for _ in 0..3 {
+            if header.attr("aria-sort").await?.as_deref() == Some(aria_sort) {
+                return Ok(true);
+            }
+
+            header.click().await?;
+        }
+
+        warn!("failed to sort members table column {}", column);
+
+        Ok(false)
+    }
+
+    /// Parses every row currently shown in the members table.
+    async fn read_member_rows(&self) -> Result<Vec<Member>, SumsClientMembersError> {
+        self.member_rows().try_collect().await
+    }
+
+    /// Parses the rows currently shown in the members table one at a time.
+    fn member_rows(&self) -> impl Stream<Item = Result<Member, SumsClientMembersError>> + '_ {
+        self.member_rows_with(&MemberFields::ALL)
+    }
+
+    /// Like [`SumsClient::member_rows`], but only reads the columns for
+    /// `fields`.
+    fn member_rows_with<'a>(
+        &'a self,
+        fields: &'a MemberFields,
+    ) -> impl Stream<Item = Result<Member, SumsClientMembersError>> + 'a {
+        browser::member_rows(self, self.date_format.as_deref(), fields)
+            .or_else(move |err| async move { Err(self.capture_page_source(err).await) })
+    }
+
+    /// Works out where each column of the members table is from its headers.
+    async fn member_column_indices(&self) -> Result<MemberColumns, SumsClientMembersError> {
+        self.with_page_source(browser::member_columns(self).await)
+            .await
+    }
+
+    /// Passes `result` through [`SumsClient::capture_page_source`] if it's an
+    /// error.
+    async fn with_page_source<T>(
+        &self,
+        result: Result<T, SumsClientMembersError>,
+    ) -> Result<T, SumsClientMembersError> {
+        match result {
+            Ok(value) => Ok(value),
+            Err(err) => Err(self.capture_page_source(err).await),
+        }
+    }
+
+    /// Attaches the current page's HTML to a navigation or parse failure as
+    /// its `context`, if [`SumsClient::with_capture_on_error`] is on. Not
+    /// being able to get the HTML just leaves it out, since the original
+    /// error is what matters.
+    async fn capture_page_source(&self, mut err: SumsClientMembersError) -> SumsClientMembersError {
+        if !self.capture_on_error {
+            return err;
+        }
+
+        if let SumsClientMembersError::NavigationFailed { context, .. }
+        | SumsClientMembersError::ParseFailed { context, .. } = &mut err
+        {
+            // The source of a new session wouldn't say anything about the
+            // error, so this doesn't reconnect
+            if context.is_none() {
+                match self.client().source().await {
+                    Ok(source) => *context = Some(source),
+                    Err(source_err) => warn!("failed to capture the page source: {}", source_err),
+                }
+            }
+        }
+
+        err
+    }
+
+    /// Types `query` into the members table's search box, and waits for the
+    /// table to be filtered.
+    async fn search_members_table(
+        &self,
+        query: &str,
+    ) -> Result<EntriesInfo, SumsClientMembersError> {
+        let previous_info_text = self.entries_info_text().await?;
+
+        let search_box = self
+            .wait_for(self.selectors.members_search.locator())
+            .await?;
+        search_box.clear().await?;
+        search_box.send_keys(query).await?;
+
+        self.wait_for_redraw(&previous_info_text).await
+    }
+
+    /// Waits for the members table to be redrawn after a search or page
+    /// change, by waiting until the entries info text differs from
+    /// `previous_info_text`. If it never changes, whatever we did presumably
+    /// didn't change what's shown, so we carry on with whatever's there.
+    async fn wait_for_redraw(
+        &self,
+        previous_info_text: &str,
+    ) -> Result<EntriesInfo, SumsClientMembersError> {
+        self.wait_for_table_redraw(MEMBERS_TABLE_ID, previous_info_text)
+            .await
+    }
+
+    /// Like [`SumsClient::wait_for_redraw`], but for any of SUMS's
+    /// DataTables, given the ID of its `<table>`.
+    async fn wait_for_table_redraw(
+        &self,
+        table_id: &str,
+        previous_info_text: &str,
+    ) -> Result<EntriesInfo, SumsClientMembersError> {
+        let deadline = Instant::now() + self.timeout;
+        let mut info_text = self.table_info_text(table_id).await?;
+
+        while info_text == previous_info_text && Instant::now() < deadline {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            info_text = self.table_info_text(table_id).await?;
+        }
+
+        EntriesInfo::parse(&info_text)
+            .ok_or(SumsClientMembersError::UnexpectedEntriesInfo(info_text))
+    }
+
+    /// Builds the URL of a page on the student dashboard from its path.
+    fn dashboard_page(&self, path: &str) -> String {
+        format!(
+            "{}/{}",
+            self.dashboard_url.as_str().trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+
+    /// Runs `op`, retrying it with exponential backoff and jitter if it fails
+    /// with an error that `is_retryable` accepts, up to the client's maximum
+    /// number of retries.
+    ///
+    /// Only wrap single steps like a navigation or a click in this, not
+    /// whole flows made of steps that already retry, or the retries multiply.
+    async fn retry<T, E, F, Fut, P>(&self, is_retryable: P, mut op: F) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        P: Fn(&E) -> bool,
+    {
+        let mut attempt = 0;
+
+        loop {
+            match op().await {
+                Err(err) if attempt < self.max_retries && is_retryable(&err) => {
+                    let delay = RETRY_BASE_DELAY
+                        .saturating_mul(2u32.saturating_pow(attempt))
+                        .min(RETRY_MAX_DELAY);
+                    let jitter = delay.mul_f64(fastrand::f64() * 0.5);
+
+                    tokio::time::sleep(delay + jitter).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Runs a WebDriver command on the current session. If that fails because
+    /// the session's been lost and [`SumsClient::reconnect`] replaces it, the
+    /// command is run once more on the new session.
+    ///
+    /// Everything that talks to the browser through the client rather than
+    /// an element goes through here, so whichever command first finds the
+    /// session gone reconnects. Elements from the old session stay dead.
+    async fn command<T, F, Fut>(&self, mut command: F) -> Result<T, CmdError>
+    where
+        F: FnMut(Client) -> Fut,
+        Fut: Future<Output = Result<T, CmdError>>,
+    {
+        match command(self.client()).await {
+            Err(err) if is_session_lost(&err) && self.reconnect().await => {
+                command(self.client()).await
+            }
+            result => result,
+        }
+    }
+
+    /// The URL of the current page.
+    async fn current_url(&self) -> Result<Url, CmdError> {
+        self.command(|client| async move { client.current_url().await })
+            .await
+    }
+
+    /// Waits for an element to appear on the page, polling until it's found or
+    /// the client's timeout elapses.
+    async fn wait_for(&self, locator: Locator<'_>) -> Result<Element, CmdError> {
+        let result = self
+            .command(|client| async move {
+                client
+                    .wait()
+                    .at_most(self.timeout)
+                    .for_element(locator)
+                    .await
+            })
+            .await;
+
+        if let Err(err) = &result {
+            warn!("failed to find {:?}: {}", locator, err);
+        }
+
+        result
+    }
+
+    /// Navigates to `url`, logging where we went and how long it took. If a
+    /// minimum interval is set, this waits until it's been that long since
+    /// the last navigation first.
+    async fn goto(&self, url: &str) -> Result<(), CmdError> {
+        {
+            // Hold the lock while sleeping, so concurrent navigations queue up
+            let mut last_navigation = self.last_navigation.lock().await;
+            let delay = throttle_delay(*last_navigation, Instant::now(), self.min_interval);
+
+            if !delay.is_zero() {
+                debug!("waiting {:?} before navigating to {}", delay, url);
+                tokio::time::sleep(delay).await;
+            }
+
+            *last_navigation = Some(Instant::now());
+        }
+
+        let started = Instant::now();
+
+        match self
+            .command(|client| async move { client.goto(url).await })
+            .await
+        {
+            Ok(()) => {
+                debug!("navigated to {} in {:?}", url, started.elapsed());
+                Ok(())
+            }
+            Err(err) => {
+                warn!("failed to navigate to {}: {}", url, err);
+                Err(err)
+            }
+        }
+    }
+
+    /// Checks that the current page isn't SUMS's maintenance page, which
+    /// would otherwise show up as whatever element we look for next being
+    /// missing.
+    async fn check_service_available(&self) -> Result<(), SumsClientError> {
+        let title = self
+            .command(|client| async move { client.title().await })
+            .await?;
+
+        if is_maintenance_title(&title)
+            || self
+                .try_find(Locator::Css(MAINTENANCE_BANNER))
+                .await?
+                .is_some()
+        {
+            warn!("SUMS is showing its maintenance page, titled {:?}", title);
+
+            return Err(SumsClientError::ServiceUnavailable);
+        }
+
+        Ok(())
+    }
+
+    /// Accepts the SU website's cookie banner if it's showing, since it can
+    /// sit on top of the user menu and swallow clicks meant for it. It's only
+    /// shown until it's been answered, so usually there's nothing to do.
+    async fn dismiss_cookie_banner(&self) -> Result<(), CmdError> {
+        let Some(button) = self
+            .try_find(self.selectors.cookie_banner_dismiss.locator())
+            .await?
+        else {
+            return Ok(());
+        };
+
+        // The banner can be in the page but hidden, or still animating in,
+        // and failing to close it shouldn't stop us trying to carry on
+        if !button.is_displayed().await? {
+            return Ok(());
+        }
+
+        match button.click().await {
+            Ok(()) => debug!("dismissed the cookie banner"),
+            Err(err) => warn!("failed to dismiss the cookie banner: {}", err),
+        }
+
+        Ok(())
+    }
+
+    /// Looks for an element without waiting for it to appear, returning `None`
+    /// if it isn't on the page.
+    async fn try_find(&self, locator: Locator<'_>) -> Result<Option<Element>, CmdError> {
+        match self
+            .command(|client| async move { client.find(locator).await })
+            .await
+        {
+            Ok(element) => Ok(Some(element)),
+            Err(CmdError::NoSuchElement(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Opens the user actions menu on the SU site and finds the logout link in
+    /// it, if there is one.
+    async fn find_logout_link(&self) -> Result<Option<Element>, SumsClientError> {
+        self.goto(self.base_url.as_str()).await?;
+        self.check_service_available().await?;
+        self.dismiss_cookie_banner().await?;
+
+        self.ensure_user_menu_open().await?;
+
+        Ok(self.try_find(self.selectors.logout_link.locator()).await?)
+    }
+
+    /// Opens the user actions menu on the SU site, unless it's already open.
+    /// The button toggles the menu, so blindly clicking it would close a menu
+    /// left open by an earlier step.
+    async fn ensure_user_menu_open(&self) -> Result<(), CmdError> {
+        let invoker = self.wait_for(self.selectors.user_menu.locator()).await?;
+
+        if is_expanded(invoker.attr("aria-expanded").await?.as_deref()) {
+            debug!("user menu is already open");
+            return Ok(());
+        }
+
+        invoker.click().await
+    }
+
+    /// Navigates to one of a group's pages on the student dashboard, such as
+    /// `members`. If the user can't access the group, or that page of it,
+    /// SUMS redirects elsewhere, so this checks we actually ended up on the
+    /// page we asked for.
+    async fn goto_group_page(&self, group_id: u16, page: &str) -> Result<(), SumsClientError> {
+        let page_path = if page.is_empty() {
+            format!("groups/{}", group_id)
+        } else {
+            format!("groups/{}/{}", group_id, page)
+        };
+
+        let page_url = self.dashboard_page(&page_path);
+        self.retry(is_transient, || self.goto(&page_url)).await?;
+        self.check_service_available().await?;
+
+        let current_url = self.current_url().await?;
+
+        if current_url.host_str() == self.dashboard_url.host_str()
+            && is_on_page(current_url.path(), &page_path)
+        {
+            Ok(())
+        } else {
+            warn!(
+                "tried to open {} of group {}, ended up at {}",
+                page_path, group_id, current_url
+            );
+
+            Err(SumsClientError::GroupInaccessible(group_id))
+        }
+    }
+
+    /// Navigates to the group's members table on the student dashboard.
+    async fn go_to_members_table(&self, group_id: u16) -> Result<(), SumsClientError> {
+        self.go_to_member_page().await?;
+
+        self.goto_group_page(group_id, "members").await
+    }
+
+    /// Navigates to one of the client's group's pages like
+    /// [`SumsClient::goto_group_page`], reporting a failure as a navigation
+    /// failure of `step`.
+    async fn open_group_page(
+        &self,
+        page: &str,
+        step: &'static str,
+    ) -> Result<(), SumsClientMembersError> {
+        let result = match self.go_to_member_page().await {
+            Ok(()) => self.goto_group_page(self.group_id, page).await,
+            Err(err) => Err(err),
+        };
+
+        self.with_page_source(result.map_err(navigation_failed(step)))
+            .await
+    }
+
+    /// Reads the "Showing X to Y of Z entries" text under the members table.
+    async fn entries_info(&self) -> Result<EntriesInfo, SumsClientMembersError> {
+        browser::entries_info(self).await
+    }
+
+    /// The raw text behind [`SumsClient::entries_info`].
+    async fn entries_info_text(&self) -> Result<String, CmdError> {
+        self.table_info_text(MEMBERS_TABLE_ID).await
+    }
+
+    /// Navigates to the student dashboard through the SU website, which is
+    /// what logs the dashboard in. If the browser's already on the dashboard
+    /// and [`SumsClient::with_reuse_dashboard`] has turned that on, this does
+    /// nothing.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    async fn go_to_member_page(&self) -> Result<(), SumsClientError> {
+        let started = Instant::now();
+
+        if self.reuse_dashboard && is_dashboard_url(&self.current_url().await?, &self.dashboard_url)
+        {
+            debug!("already on the student dashboard, not navigating there again");
+
+            return Ok(());
+        }
+
+        self.retry(is_transient, || self.goto(self.base_url.as_str()))
+            .await?;
+        self.check_service_available().await?;
+        self.dismiss_cookie_banner().await?;
+
+        self.retry(is_transient, || async move {
+            self.ensure_user_menu_open().await?;
+
+            self.wait_for(self.selectors.dashboard_link.locator())
+                .await?
+                .click()
+                .await
+        })
+        .await?;
+
+        // This used to wait for the exact dashboard URL, but the dashboard
+        // sometimes lands on a path under it or adds a query string, which
+        // made the wait time out even though we'd got there
+        let deadline = Instant::now() + self.timeout;
+
+        loop {
+            let current_url = self.current_url().await?;
+
+            if is_dashboard_url(&current_url, &self.dashboard_url) {
+                break;
+            }
+
+            if Instant::now() >= deadline {
+                warn!(
+                    "timed out waiting for the student dashboard, ended up at {}",
+                    current_url
+                );
+
+                return Err(CmdError::WaitTimeout.into());
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        debug!("reached the student dashboard in {:?}", started.elapsed());
+
+        Ok(())
+    }
+}
+
+/// How long to wait before navigating at `now`, so that navigations are at
+/// least `min_interval` apart.
+fn throttle_delay(
+    last_navigation: Option<Instant>,
+    now: Instant,
+    min_interval: Duration,
+) -> Duration {
+    match last_navigation {
+        Some(last_navigation) => (last_navigation + min_interval).saturating_duration_since(now),
+        None => Duration::ZERO,
+    }
+}
+
+/// Reads a credential from the environment variable `name`.
+fn credential_from_env(name: &'static str) -> Result<String, SumsClientAuthError> {
+    std::env::var(name).map_err(|_| SumsClientAuthError::MissingCredential(name))
+}
+
+/// Whether `url` is on the student dashboard at `dashboard_url`, ignoring any
+/// path under it, query string or fragment.
+fn is_dashboard_url(url: &Url, dashboard_url: &Url) -> bool {
+    url.scheme() == dashboard_url.scheme()
+        && url.host_str() == dashboard_url.host_str()
+        && url.port_or_known_default() == dashboard_url.port_or_known_default()
+        && match dashboard_url.path().trim_matches('/') {
+            "" => true,
+            dashboard_path => is_on_page(url.path(), dashboard_path),
+        }
+}
+
+/// Compares the number of members read with the number the members table
+/// says it has, warning about a mismatch or, if `strict`, returning it as an
+/// error.
+fn check_member_count(
+    expected: usize,
+    found: usize,
+    strict: bool,
+) -> Result<(), SumsClientMembersError> {
+    if expected == found {
+        return Ok(());
+    }
+
+    if strict {
+        return Err(SumsClientMembersError::MemberCountMismatch { expected, found });
+    }
+
+    warn!(
+        "the members table says there are {} members, but {} were read",
+        expected, found
+    );
+
+    Ok(())
+}
+
+/// Whether an element's `aria-expanded` attribute says it's expanded. A
+/// missing attribute means it isn't.
+fn is_expanded(aria_expanded: Option<&str>) -> bool {
+    aria_expanded.is_some_and(|value| value.trim().eq_ignore_ascii_case("true"))
+}
+
+/// Whether a page title looks like one of SUMS's maintenance or outage pages.
+fn is_maintenance_title(title: &str) -> bool {
+    let title = title.to_lowercase();
+
+    [
+        "maintenance",
+        "service unavailable",
+        "temporarily unavailable",
+    ]
+    .iter()
+    .any(|phrase| title.contains(phrase))
+}
+
+/// Gets the group ID out of the path of a group page, like `/groups/213` or
+/// `/groups/213/members`.
+fn group_id_from_path(path: &str) -> Option<u16> {
+    let mut segments = path.trim_matches('/').split('/');
+
+    match (segments.next(), segments.next()) {
+        (Some("groups"), Some(group_id)) => group_id.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Whether a URL path is `page_path` or somewhere under it, so that
+/// `groups/1` doesn't match `groups/12`.
+fn is_on_page(path: &str, page_path: &str) -> bool {
+    let path = path.trim_matches('/');
+
+    path == page_path
+        || path
+            .strip_prefix(page_path)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Tags an error from getting to a page with the step that failed. Groups
+/// that can't be accessed keep their own variant, since retrying won't help.
+fn navigation_failed(step: &'static str) -> impl FnOnce(SumsClientError) -> SumsClientMembersError {
+    move |err| match err {
+        SumsClientError::GroupInaccessible(group_id) => {
+            SumsClientMembersError::GroupInaccessible(group_id)
+        }
+        source => SumsClientMembersError::NavigationFailed {
+            step,
+            source,
+            context: None,
+        },
+    }
+}
+
+/// Tags an error from parsing some of a page with what was being parsed.
+fn parse_failed(
+    step: &'static str,
+) -> impl FnOnce(SumsClientMembersError) -> SumsClientMembersError {
+    move |err| SumsClientMembersError::ParseFailed {
+        step,
+        source: Box::new(err),
+        context: None,
+    }
+}
+
+/// Turns an error from scraping one of the group's pages other than the
+/// members table into a [`SumsClientError`], since those pages aren't about
+/// members. Navigation and parse failures keep their step and any captured
+/// page source, and anything else about the page's layout is reported as a
+/// parse failure of `page`.
+fn page_error(page: &'static str) -> impl FnOnce(SumsClientMembersError) -> SumsClientError {
+    move |err| match err {
+        SumsClientMembersError::SumsClientError(err) => err,
+        SumsClientMembersError::GroupInaccessible(group_id) => {
+            SumsClientError::GroupInaccessible(group_id)
+        }
+        SumsClientMembersError::ScriptError(message) => SumsClientError::ScriptError(message),
+        SumsClientMembersError::NavigationFailed {
+            step,
+            source,
+            context,
+        } => SumsClientError::NavigationFailed {
+            step,
+            source: Box::new(source),
+            context,
+        },
+        SumsClientMembersError::ParseFailed {
+            step,
+            source,
+            context,
+        } => SumsClientError::ParseFailed {
+            step,
+            reason: source.to_string(),
+            context,
+        },
+        err => SumsClientError::ParseFailed {
+            step: page,
+            reason: err.to_string(),
+            context: None,
+        },
+    }
+}
+
+/// Reports JavaScript exceptions from an injected script as a
+/// [`SumsClientMembersError::ScriptError`], and anything else as it normally
+/// would be.
+fn script_error(err: CmdError) -> SumsClientMembersError {
+    match err {
+        CmdError::Standard(ref err) if err.error() == "javascript error" => {
+            SumsClientMembersError::ScriptError(err.message.to_string())
+        }
+        err => err.into(),
+    }
+}
+
+/// Whether a WebDriver command failure is likely to go away if the command is
+/// retried, such as a stale element or a dropped connection.
+fn is_transient(err: &CmdError) -> bool {
+    match err {
+        CmdError::Lost(_) | CmdError::Failed(_) => true,
+        // fantoccini reports stale elements as NoSuchElement too. Elements
+        // that are actually missing have already been waited for, so trying
+        // again would only wait for them again
+        CmdError::NoSuchElement(err) | CmdError::Standard(err) => {
+            err.error() == "stale element reference"
+        }
+        _ => false,
+    }
+}
+
+/// Whether a WebDriver command failed because the session is gone, like when
+/// the browser crashed or the WebDriver server restarted.
+fn is_session_lost(err: &CmdError) -> bool {
+    match err {
+        CmdError::Lost(_) | CmdError::Failed(_) => true,
+        CmdError::Standard(err) => err.error() == "invalid session id",
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, time::Duration};
+
+    use chrono::NaiveDate;
+    use fantoccini::error::CmdError;
+    use fantoccini::wd::Capabilities;
+    use serde_json::json;
+    use tokio::time::Instant;
+    use url::Url;
+
+    use crate::academic_year::AcademicYear;
+    use crate::client::{SumsClient, SumsClientAuthError, SumsClientNewError, SumsClientPool};
+    use crate::event::EventFilter;
+    use crate::member::{MemberFields, MemberSort};
+
+    use super::{
+        check_member_count, credential_from_env, group_id_from_path, is_dashboard_url, is_expanded,
+        is_maintenance_title, is_on_page, is_session_lost, navigation_failed, page_error,
+        parse_failed, script_error, throttle_delay, SumsClientError, SumsClientMembersError,
+    };
+
+    const GROUP_ID: u16 = 213;
+    const WEBDRIVER_ADDRESS: &str = "http://localhost:9515";
+
+    #[tokio::test]
+    async fn test_create_client() -> Result<(), SumsClientNewError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium").await?;
+
+        assert_eq!(GROUP_ID, client.group_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_create_client_with_capabilities() -> Result<(), SumsClientNewError> {
+        let mut capabilities = Capabilities::new();
+        capabilities.insert("browserName".to_string(), "chromium".into());
+        capabilities.insert(
+            "goog:chromeOptions".to_string(),
+            json!({ "args": ["--headless=new"] }),
+        );
+
+        let client =
+            SumsClient::with_capabilities(GROUP_ID, WEBDRIVER_ADDRESS, capabilities).await?;
+
+        assert_eq!(GROUP_ID, client.group_id);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auth() -> Result<(), SumsClientAuthError> {
+        // test_create_client should handle this
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client.authenticate(username, password).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ping() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        client.ping().await
+    }
+
+    #[tokio::test]
+    async fn test_auth_timeout() {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap()
+            .with_auth_timeout(Duration::from_millis(1));
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        assert!(matches!(
+            client.authenticate(username, password).await,
+            Err(SumsClientAuthError::Timeout(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_auth_twice() -> Result<(), SumsClientAuthError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client.authenticate(&username, &password).await?;
+        client.authenticate(&username, &password).await?;
+
+        assert!(client.is_authenticated().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auth_from_env() -> Result<(), SumsClientAuthError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        client.authenticate_from_env().await
+    }
+
+    #[tokio::test]
+    async fn test_auth_with_mfa() -> Result<(), SumsClientAuthError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        // The test account doesn't have two-factor authentication set up
+        client
+            .authenticate_with_mfa(username, password, || {
+                unreachable!("Asked for a one-time code")
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_export_and_restore_session() -> Result<(), SumsClientAuthError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client.authenticate(&username, &password).await?;
+        let state = client.export_session().await?;
+        client.close().await?;
+
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        assert!(client.restore_session(&state).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_session_cookies() -> Result<(), SumsClientAuthError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client.authenticate(&username, &password).await?;
+
+        let cookies = client.session_cookies().await?;
+
+        assert!(!cookies.is_empty());
+        assert!(client.is_authenticated().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect() -> Result<(), SumsClientAuthError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap()
+            .with_auto_reconnect(true);
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client.authenticate(&username, &password).await?;
+
+        // Pull the session out from under the client, like a browser crash
+        client.raw_client().close().await?;
+
+        assert!(client.is_authenticated().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_screenshot() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let png = client.screenshot().await?;
+
+        assert!(png.starts_with(b"\x89PNG"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_goto_dashboard_path() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let path = format!("groups/{}/members", GROUP_ID);
+        client.goto_dashboard_path(&path).await?;
+
+        let current_url = client.raw_client().current_url().await?;
+        assert!(current_url.path().contains(&path));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_goto_dashboard_path_logged_out() {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let path = format!("groups/{}/members", GROUP_ID);
+
+        assert!(matches!(
+            client.goto_dashboard_path(&path).await,
+            Err(SumsClientError::NotLoggedIn(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_script() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let result = client
+            .execute_script("return arguments[0] + 1;", vec![json!(1)])
+            .await?;
+        assert_eq!(result, json!(2));
+
+        assert_eq!(client.execute_script("1 + 1;", vec![]).await?, json!(null));
+
+        let result = client
+            .execute_script("throw new Error('nope');", vec![])
+            .await;
+        assert!(matches!(result, Err(SumsClientError::ScriptError(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_events() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let all = client.events(EventFilter::All).await?;
+        let upcoming = client.events(EventFilter::Upcoming).await?;
+        let past = client.events(EventFilter::Past).await?;
+
+        assert_eq!(all.len(), upcoming.len() + past.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_products() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        client.products().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_with_fields() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let fields = MemberFields {
+            name: true,
+            ..MemberFields::NONE
+        };
+        let members = client.members_with_fields(fields).await?;
+
+        assert_eq!(members.len(), client.member_count().await?);
+        assert!(members
+            .iter()
+            .all(|member| member.subscription_purchased.is_none()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_capture_on_error() {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap()
+            .with_capture_on_error(true);
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        // Nothing can load this quickly, so getting to the members table fails
+        let client = client.with_timeout(Duration::from_millis(1));
+        let err = client.members().await.unwrap_err();
+
+        assert!(err.is_navigation_failure());
+        assert!(err.context().is_some_and(|html| html.contains("<html")));
+    }
+
+    #[tokio::test]
+    async fn test_transactions() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let from = NaiveDate::from_ymd_opt(2023, 9, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+
+        for transaction in client.transactions(from, to).await? {
+            assert!((from..=to).contains(&transaction.date));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_sorted() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let ascending = client
+            .members_sorted(MemberSort::ByDateJoined, true)
+            .await?;
+        assert!(ascending
+            .windows(2)
+            .all(|pair| pair[0].date_joined <= pair[1].date_joined));
+
+        let descending = client
+            .members_sorted(MemberSort::ByDateJoined, false)
+            .await?;
+        assert!(descending
+            .windows(2)
+            .all(|pair| pair[0].date_joined >= pair[1].date_joined));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_auth_detailed() -> Result<(), SumsClientAuthError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        let auth_info = client.authenticate_detailed(username, password).await?;
+
+        assert!(auth_info.display_name.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_by_id() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let members = client.members_by_id().await?;
+
+        assert!(members
+            .iter()
+            .all(|(student_id, member)| *student_id == member.student_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_members() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let exported = client.export_members().await?;
+        let scraped = client.members().await?;
+
+        assert_eq!(exported.len(), scraped.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_export_members_csv_streaming() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let mut output = Vec::new();
+        client.export_members_csv_streaming(&mut output).await?;
+        let members = client.members().await?;
+
+        // One line for the header, plus one per member
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(
+            csv::Reader::from_reader(output.as_bytes())
+                .records()
+                .count(),
+            members.len()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pool_members_for_groups() {
+        let builder = SumsClient::builder()
+            .group_id(GROUP_ID)
+            .webdriver_address(WEBDRIVER_ADDRESS);
+
+        let pool = SumsClientPool::from_builder(builder, 2).await.unwrap();
+
+        pool.authenticate_from_env().await.expect("Auth failed");
+
+        let results = pool.members_for_groups(&[GROUP_ID, u16::MAX]).await;
+
+        assert!(results[&GROUP_ID].is_ok());
+        assert!(matches!(
+            results[&u16::MAX],
+            Err(SumsClientMembersError::GroupInaccessible(u16::MAX))
+        ));
+
+        pool.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_members_with_roles() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let members = client.members_with_roles().await?;
+
+        assert!(members.iter().any(|(_, role)| role.is_some()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_administered_groups() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let groups = client.administered_groups().await?;
+
+        assert!(groups.iter().any(|group| group.id == GROUP_ID));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_back_to_back() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap()
+            .with_reuse_dashboard(true);
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        // With reuse on, the second call starts on the dashboard, so skips
+        // the SU website
+        let first = client.members().await?;
+        let second = client.members().await?;
+
+        assert_eq!(first.len(), second.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_member() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let member = client.members().await?.remove(0);
+        let found = client
+            .wait_for_member(member.student_id.as_str(), Duration::from_secs(60))
+            .await?;
+        assert_eq!(found, member);
+
+        assert!(matches!(
+            client
+                .wait_for_member("00000000", Duration::from_secs(15))
+                .await,
+            Err(SumsClientMembersError::Timeout(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_member_type_counts() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let counts = client.member_type_counts().await?;
+
+        assert_eq!(
+            counts.values().sum::<usize>(),
+            client.members().await?.len()
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_is_member() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let today = chrono::Local::now().date_naive();
+        let members = client.members().await?;
+        let expected = members.first().expect("Group has no members");
+
+        assert_eq!(
+            client.is_member(expected.student_id.as_str()).await?,
+            expected.is_subscription_active(today)
+        );
+        assert!(!client.is_member("00000000").await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_with_progress() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let mut calls = Vec::new();
+        let members = client
+            .members_with_progress(|done, total| calls.push((done, total)))
+            .await?;
+
+        assert_eq!(calls.len(), members.len());
+        for (i, (done, total)) in calls.into_iter().enumerate() {
+            assert_eq!(done, i + 1);
+            assert_eq!(total, Some(members.len()));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_in_year() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let year = AcademicYear::containing(chrono::Local::now().date_naive());
+
+        for member in client.members_in_year(year).await? {
+            assert!(year.contains(member.date_joined));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_member_count() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let member_count = client.member_count().await?;
+
+        assert_eq!(member_count, client.members().await?.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_member_by_id() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let members = client.members().await?;
+        let expected = members.first().expect("Group has no members");
+
+        let member = client
+            .member_by_id(expected.student_id.as_str())
+            .await?
+            .expect("Member not found");
+        assert_eq!(member.student_id, expected.student_id);
+
+        assert!(client.member_by_id("00000000").await?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_search_members() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let members = client.members().await?;
+        let expected = members.first().expect("Group has no members");
+
+        let found = client.search_members(&expected.name).await?;
+        assert!(found
+            .iter()
+            .any(|member| member.student_id == expected.student_id));
+
+        assert!(client
+            .search_members("this should not match anybody")
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_page() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let member_count = client.member_count().await?;
+
+        let first_page = client.members_page(0, 10).await?;
+        assert_eq!(first_page.len(), member_count.min(10));
+
+        assert!(client
+            .members_page(member_count / 10 + 1, 10)
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_group_info() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let group_info = client.group_info().await?;
+
+        assert_eq!(group_info.id, GROUP_ID);
+        assert!(!group_info.name.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_committee() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let committee = client.committee().await?;
+
+        assert!(committee
+            .iter()
+            .all(|committee_member| !committee_member.name.is_empty()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_logout() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        client.logout().await?;
+
+        // Logging out again should be a no-op
+        client.logout().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        client.members().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_member_columns() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let columns = client.member_columns().await?;
+
+        assert!(columns.iter().any(|column| column == "Name"));
+        assert!(columns.iter().all(|column| column.trim() == column));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_table_html() -> Result<(), SumsClientError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        let html = client.members_table_html().await?;
+
+        assert!(html.contains("group-member-list-datatable"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_members_twice() -> Result<(), SumsClientMembersError> {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        // Injecting the script again on the same page shouldn't break anything
+        let first = client.members().await?;
+        let second = client.members().await?;
+
+        assert_eq!(first.len(), second.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_script_error_passes_through_other_errors() {
+        let err = script_error(CmdError::NotJson("not json".to_string()));
+
+        assert!(matches!(
+            err,
+            SumsClientMembersError::SumsClientError(SumsClientError::WebDriverCmdError(
+                CmdError::NotJson(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_members_for_inaccessible_group() {
+        let client = SumsClient::new(GROUP_ID, WEBDRIVER_ADDRESS, "chromium")
+            .await
+            .unwrap();
+
+        let username = env::var("SUMS_USERNAME").expect("Invalid username environment variable");
+        let password = env::var("SUMS_PASSWORD").expect("Invalid password environment variable");
+
+        client
+            .authenticate(username, password)
+            .await
+            .expect("Auth failed");
+
+        assert!(matches!(
+            client.members_for(u16::MAX).await,
+            Err(SumsClientMembersError::GroupInaccessible(u16::MAX))
+        ));
+    }
+
+    #[test]
+    fn test_missing_credential() {
+        assert!(matches!(
+            credential_from_env("LIBSUMS_TEST_UNSET_VARIABLE"),
+            Err(SumsClientAuthError::MissingCredential(
+                "LIBSUMS_TEST_UNSET_VARIABLE"
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_is_dashboard_url() {
+        let dashboard = Url::parse("https://student-dashboard.sums.su").unwrap();
+        let is_dashboard = |url: &str| is_dashboard_url(&Url::parse(url).unwrap(), &dashboard);
+
+        assert!(is_dashboard("https://student-dashboard.sums.su/"));
+        assert!(is_dashboard("https://student-dashboard.sums.su/?welcome=1"));
+        assert!(is_dashboard(
+            "https://student-dashboard.sums.su/groups/213#top"
+        ));
+        assert!(!is_dashboard("https://su.nottingham.ac.uk/"));
+        assert!(!is_dashboard("http://student-dashboard.sums.su/"));
+
+        let mirror = Url::parse("http://localhost:8080/dashboard").unwrap();
+        assert!(is_dashboard_url(
+            &Url::parse("http://localhost:8080/dashboard/groups?page=2").unwrap(),
+            &mirror
+        ));
+        assert!(!is_dashboard_url(
+            &Url::parse("http://localhost:8080/dashboards").unwrap(),
+            &mirror
+        ));
+    }
+
+    #[test]
+    fn test_check_member_count() {
+        assert!(check_member_count(10, 10, true).is_ok());
+        assert!(check_member_count(10, 9, false).is_ok());
+        assert!(matches!(
+            check_member_count(10, 9, true),
+            Err(SumsClientMembersError::MemberCountMismatch {
+                expected: 10,
+                found: 9
+            })
+        ));
+    }
+
+    #[test]
+    fn test_is_session_lost() {
+        assert!(is_session_lost(&CmdError::Lost(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "connection reset"
+        ))));
+        assert!(!is_session_lost(&CmdError::NotJson("<html>".to_string())));
+    }
+
+    #[test]
+    fn test_page_error() {
+        let err = page_error("the events page")(SumsClientMembersError::ParseFailed {
+            step: "an events table row",
+            source: Box::new(SumsClientMembersError::UnexpectedTableLayout {
+                expected: 4,
+                found: 3,
+            }),
+            context: Some("<html></html>".to_string()),
+        });
+        assert_eq!(err.step(), Some("an events table row"));
+        assert_eq!(err.context(), Some("<html></html>"));
+
+        let err = page_error("the events page")(SumsClientMembersError::NoPageSizeOptions);
+        assert_eq!(err.step(), Some("the events page"));
+
+        assert!(matches!(
+            page_error("the events page")(SumsClientMembersError::GroupInaccessible(213)),
+            SumsClientError::GroupInaccessible(213)
+        ));
+    }
+
+    #[test]
+    fn test_is_expanded() {
+        assert!(is_expanded(Some("true")));
+        assert!(is_expanded(Some("True")));
+        assert!(!is_expanded(Some("false")));
+        assert!(!is_expanded(Some("")));
+        assert!(!is_expanded(None));
+    }
+
+    #[test]
+    fn test_is_maintenance_title() {
+        assert!(is_maintenance_title("Scheduled Maintenance"));
+        assert!(is_maintenance_title("503 Service Unavailable"));
+        assert!(is_maintenance_title(
+            "SUMS is temporarily unavailable | University of Nottingham Students' Union"
+        ));
+        assert!(!is_maintenance_title("Student Dashboard"));
+        assert!(!is_maintenance_title(""));
+    }
+
+    #[test]
+    fn test_group_id_from_path() {
+        assert_eq!(group_id_from_path("/groups/213"), Some(213));
+        assert_eq!(group_id_from_path("/groups/213/members/"), Some(213));
+        assert_eq!(group_id_from_path("/groups"), None);
+        assert_eq!(group_id_from_path("/groups/new"), None);
+        assert_eq!(group_id_from_path("/groups/99999999"), None);
+        assert_eq!(group_id_from_path("/events/213"), None);
+    }
+
+    #[test]
+    fn test_is_on_page() {
+        assert!(is_on_page("/groups/12/members", "groups/12/members"));
+        assert!(is_on_page("/groups/12/members/", "groups/12/members"));
+        assert!(is_on_page("/groups/12/members/export", "groups/12/members"));
+        assert!(!is_on_page("/groups/123/members", "groups/12"));
+        assert!(!is_on_page("/groups/12", "groups/12/members"));
+        assert!(!is_on_page("/", "groups/12"));
+    }
+
+    #[test]
+    fn test_group_inaccessible_members_error() {
+        let err = SumsClientMembersError::from(SumsClientError::GroupInaccessible(12));
+
+        assert!(matches!(err, SumsClientMembersError::GroupInaccessible(12)));
+    }
+
+    #[test]
+    fn test_navigation_failed() {
+        let err = navigation_failed("open the members table")(SumsClientError::LogoutFailed);
+
+        assert!(err.is_navigation_failure());
+        assert!(!err.is_parse_failure());
+        assert_eq!(err.step(), Some("open the members table"));
+        assert_eq!(err.context(), None);
+
+        // Inaccessible groups aren't worth retrying, so keep their own variant
+        let err =
+            navigation_failed("open the members table")(SumsClientError::GroupInaccessible(12));
+
+        assert!(matches!(err, SumsClientMembersError::GroupInaccessible(12)));
+        assert!(!err.is_navigation_failure());
+    }
+
+    #[test]
+    fn test_parse_failed() {
+        let err = parse_failed("a members table row")(SumsClientMembersError::InvalidStudentId(
+            "123".to_string(),
+        ));
+
+        assert!(err.is_parse_failure());
+        assert!(!err.is_navigation_failure());
+        assert_eq!(err.step(), Some("a members table row"));
+        assert!(matches!(
+            err,
+            SumsClientMembersError::ParseFailed { source, .. }
+                if matches!(*source, SumsClientMembersError::InvalidStudentId(_))
+        ));
+    }
+
+    #[test]
+    fn test_throttle_delay() {
+        let now = Instant::now();
+        let second = Duration::from_secs(1);
+
+        assert_eq!(throttle_delay(None, now, second), Duration::ZERO);
+        assert_eq!(
+            throttle_delay(Some(now), now, Duration::ZERO),
+            Duration::ZERO
+        );
+        assert_eq!(
+            throttle_delay(Some(now), now + Duration::from_millis(400), second),
+            Duration::from_millis(600)
+        );
+        assert_eq!(
+            throttle_delay(Some(now), now + second * 2, second),
+            Duration::ZERO
+        );
     }
 }
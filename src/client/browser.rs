@@ -0,0 +1,703 @@
+//! A narrow seam between scraping SUMS's tables and driving the browser, so
+//! that [`SumsClient::members`] can be tested offline against saved HTML.
+//!
+//! [`scrape_members`] is the whole of what [`SumsClient::members`] and the
+//! other members streams do, from opening the members table to checking the
+//! member count, and only talks to the page through [`Browser`]. Every other
+//! table scraper reads its rows through [`Browser`] too.
+//!
+//! [`SumsClient`] itself isn't generic over [`Browser`], since that would
+//! mean abstracting every element, form and script it uses. Instead, the
+//! steps behind [`Browser::open_members_table`] (logging the dashboard in and
+//! finding the group's page), logging in, and the pages other than the
+//! members table drive fantoccini directly, and can only be tested against a
+//! live WebDriver.
+
+use async_stream::try_stream;
+use fantoccini::{error::CmdError, Locator};
+use futures::{future, Stream, TryStreamExt};
+use url::Url;
+
+use std::collections::HashSet;
+
+use tokio::time::Instant;
+
+use crate::member::{Member, MemberFields};
+
+use super::table::{self, EntriesInfo, MemberColumns};
+use super::{
+    check_member_count, is_transient, navigation_failed, parse_failed, script_error, SumsClient,
+    SumsClientMembersError, ADD_SHOW_ALL_ENTRIES_JS, MEMBERS_TABLE_ID,
+};
+
+/// One row of a table's body.
+pub(crate) struct TableRow {
+    /// The text of each cell.
+    pub cells: Vec<String>,
+    /// Where the first image in the row is from, resolved against the page's
+    /// URL, if the row has one. In the members table this is the member's
+    /// photo.
+    pub image: Option<Url>,
+}
+
+/// Which parts of each row [`Browser::table_body_rows`] should read, since
+/// each cell's text and the row's image is another WebDriver round trip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RowParts {
+    /// The indices of the cells to read the text of, or `None` for all of
+    /// them. Cells that aren't read are left empty, so the rest keep their
+    /// places.
+    pub cells: Option<Vec<usize>>,
+    /// Whether to look for an image in the row.
+    pub image: bool,
+}
+
+impl RowParts {
+    /// The text of every cell, and no image.
+    pub const CELLS: Self = Self {
+        cells: None,
+        image: false,
+    };
+
+    /// Whether the cell at `index` should be read.
+    pub fn wants_cell(&self, index: usize) -> bool {
+        self.cells
+            .as_ref()
+            .is_none_or(|cells| cells.contains(&index))
+    }
+}
+
+/// The parts of driving SUMS that scraping its tables needs. The real
+/// implementation is [`SumsClient`], and the tests serve canned HTML.
+pub(crate) trait Browser {
+    /// Navigates to a group's members table, reporting a failure as a
+    /// navigation failure.
+    async fn open_members_table(&self, group_id: u16) -> Result<(), SumsClientMembersError>;
+
+    /// Makes any of SUMS's DataTables show every row on one page, given the
+    /// ID of its `<table>`.
+    async fn show_all_table_entries(&self, table_id: &str) -> Result<(), SumsClientMembersError>;
+
+    /// Reads the "Showing X to Y of Z entries" text under any of SUMS's
+    /// DataTables, given the ID of its `<table>`.
+    async fn table_info_text(&self, table_id: &str) -> Result<String, CmdError>;
+
+    /// Reads the text of each header in a table, given the ID of its
+    /// `<table>`.
+    async fn table_headers(&self, table_id: &str) -> Result<Vec<String>, CmdError>;
+
+    /// Reads the `parts` of each row of a table's body, one row at a time,
+    /// given the ID of its `<table>`.
+    fn table_body_rows<'a>(
+        &'a self,
+        table_id: &'a str,
+        parts: &'a RowParts,
+    ) -> impl Stream<Item = Result<TableRow, CmdError>> + 'a;
+}
+
+impl Browser for SumsClient {
+    async fn open_members_table(&self, group_id: u16) -> Result<(), SumsClientMembersError> {
+        let result = self
+            .go_to_members_table(group_id)
+            .await
+            .map_err(navigation_failed("open the members table"));
+
+        self.with_page_source(result).await
+    }
+
+    async fn show_all_table_entries(&self, table_id: &str) -> Result<(), SumsClientMembersError> {
+        // DataTables only adds the page size select once it's set up the
+        // table, so wait for it before the script goes looking for it.
+        let entry_count_selector = self
+            .wait_for(Locator::Css(&format!("#{}_length select", table_id)))
+            .await?;
+
+        self.command(|client| async move {
+            client
+                .execute(ADD_SHOW_ALL_ENTRIES_JS, vec![table_id.into()])
+                .await
+        })
+        .await
+        .map_err(script_error)?;
+
+        // The script should have added an option big enough for everything,
+        // but pick whatever's biggest in case it couldn't
+        let mut option_values = Vec::new();
+
+        for option in entry_count_selector
+            .find_all(Locator::Css("option"))
+            .await?
+        {
+            if let Some(value) = option.attr("value").await? {
+                option_values.push(value);
+            }
+        }
+
+        let page_size = table::largest_page_size(&option_values)
+            .ok_or(SumsClientMembersError::NoPageSizeOptions)?;
+
+        debug!(
+            "showing {} entries per page of table {}",
+            page_size, table_id
+        );
+
+        entry_count_selector.select_by_value(page_size).await?;
+
+        Ok(())
+    }
+
+    async fn table_info_text(&self, table_id: &str) -> Result<String, CmdError> {
+        self.wait_for(Locator::Id(&format!("{}_info", table_id)))
+            .await?
+            .text()
+            .await
+    }
+
+    async fn table_headers(&self, table_id: &str) -> Result<Vec<String>, CmdError> {
+        let header_elements = self
+            .wait_for(Locator::Css(&format!("#{} > thead", table_id)))
+            .await?
+            .find_all(Locator::Css("th"))
+            .await?;
+
+        let mut headers = Vec::new();
+
+        for header_element in header_elements {
+            headers.push(table::normalize_cell(&header_element.text().await?));
+        }
+
+        Ok(headers)
+    }
+
+    fn table_body_rows<'a>(
+        &'a self,
+        table_id: &'a str,
+        parts: &'a RowParts,
+    ) -> impl Stream<Item = Result<TableRow, CmdError>> + 'a {
+        try_stream! {
+            let page_url = self.current_url().await?;
+            let table_body = format!("#{} > tbody", table_id);
+            let table_body = &table_body;
+
+            let row_elements = self
+                .retry(is_transient, || async move {
+                    self.wait_for(Locator::Css(table_body))
+                        .await?
+                        .find_all(Locator::Css("tr"))
+                        .await
+                })
+                .await?;
+
+            debug!("found {} rows in table {}", row_elements.len(), table_id);
+
+            for row_element in row_elements {
+                let mut cells = Vec::new();
+
+                for (i, cell) in row_element.find_all(Locator::Css("td")).await?.iter().enumerate() {
+                    cells.push(if parts.wants_cell(i) {
+                        table::normalize_cell(&cell.text().await?)
+                    } else {
+                        String::new()
+                    });
+                }
+
+                // find_all rather than find, so a row without an image isn't
+                // an error
+                let image = if parts.image {
+                    match row_element.find_all(Locator::Css("img")).await?.first() {
+                        Some(image) => image.attr("src").await?,
+                        None => None,
+                    }
+                } else {
+                    None
+                };
+
+                yield TableRow {
+                    cells,
+                    image: image.and_then(|src| resolve_image(&page_url, &src)),
+                };
+            }
+        }
+    }
+}
+
+/// Resolves an image's `src` against the URL of the page it's on. Blank and
+/// unparseable sources count as no image.
+fn resolve_image(page_url: &Url, src: &str) -> Option<Url> {
+    let src = src.trim();
+
+    if src.is_empty() {
+        return None;
+    }
+
+    match page_url.join(src) {
+        Ok(url) => Some(url),
+        Err(err) => {
+            warn!(
+                "ignoring an image with an invalid source {:?}: {}",
+                src, err
+            );
+            None
+        }
+    }
+}
+
+/// Reads the text of every cell in every row of a table, given the ID of its
+/// `<table>`, skipping the placeholder row shown when the table is empty.
+pub(crate) async fn table_cells<B: Browser>(
+    browser: &B,
+    table_id: &str,
+) -> Result<Vec<Vec<String>>, CmdError> {
+    browser
+        .table_body_rows(table_id, &RowParts::CELLS)
+        .map_ok(|row| row.cells)
+        .try_filter(|cells| future::ready(!table::is_empty_placeholder(cells)))
+        .try_collect()
+        .await
+}
+
+/// Works out where each column of the members table is from its headers.
+pub(crate) async fn member_columns<B: Browser>(
+    browser: &B,
+) -> Result<MemberColumns, SumsClientMembersError> {
+    let headers = browser.table_headers(MEMBERS_TABLE_ID).await?;
+
+    MemberColumns::from_headers(&headers).map_err(|err| {
+        warn!("unexpected members table headers: {:?}", headers);
+        parse_failed("the members table headers")(err)
+    })
+}
+
+/// Reads the "Showing X to Y of Z entries" text under the members table.
+pub(crate) async fn entries_info<B: Browser>(
+    browser: &B,
+) -> Result<EntriesInfo, SumsClientMembersError> {
+    let info_text = browser.table_info_text(MEMBERS_TABLE_ID).await?;
+
+    EntriesInfo::parse(&info_text).ok_or(SumsClientMembersError::UnexpectedEntriesInfo(info_text))
+}
+
+/// Opens a group's members table, shows every member, and parses them one at
+/// a time, reading only the columns for `fields`, with dates in
+/// `date_format` if it's given.
+///
+/// SUMS occasionally shows the same member twice, so rows with a student ID
+/// that's already been seen are skipped, keeping the first. Once every row
+/// is read, the number of members is checked against the count under the
+/// table with [`check_member_count`], which fails on a mismatch if `strict`.
+pub(crate) fn scrape_members<'a, B: Browser>(
+    browser: &'a B,
+    group_id: u16,
+    fields: MemberFields,
+    date_format: Option<&'a str>,
+    strict: bool,
+) -> impl Stream<Item = Result<Member, SumsClientMembersError>> + 'a {
+    try_stream! {
+        let started = Instant::now();
+        let mut seen = HashSet::new();
+
+        browser.open_members_table(group_id).await?;
+
+        browser.show_all_table_entries(MEMBERS_TABLE_ID).await?;
+
+        for await member in member_rows(browser, date_format, &fields) {
+            let member = member?;
+
+            // SUMS sometimes renders a row twice while re-sorting
+            if !seen.insert(member.student_id.clone()) {
+                debug!("skipping duplicate row for {}", member.student_id);
+                continue;
+            }
+
+            yield member;
+        }
+
+        let member_count = seen.len();
+
+        // Rows that never rendered would otherwise go missing silently
+        match entries_info(browser).await {
+            Ok(entries_info) => {
+                check_member_count(entries_info.member_count(), member_count, strict)?
+            }
+            Err(err) => debug!("couldn't read the number of members: {}", err),
+        }
+
+        debug!(
+            "scraped {} members of group {} in {:?}",
+            member_count,
+            group_id,
+            started.elapsed()
+        );
+    }
+}
+
+/// Parses the rows currently shown in the members table one at a time, with
+/// dates in `date_format` if it's given. Only the columns for `fields` are
+/// read, see [`table::parse_member_fields`].
+pub(crate) fn member_rows<'a, B: Browser>(
+    browser: &'a B,
+    date_format: Option<&'a str>,
+    fields: &'a MemberFields,
+) -> impl Stream<Item = Result<Member, SumsClientMembersError>> + 'a {
+    try_stream! {
+        let columns = member_columns(browser).await?;
+        let parts = RowParts {
+            cells: Some(columns.indices_for(fields)),
+            image: fields.avatar_url,
+        };
+
+        for await row in browser.table_body_rows(MEMBERS_TABLE_ID, &parts) {
+            let row = row?;
+
+            if table::is_empty_placeholder(&row.cells) {
+                continue;
+            }
+
+            let mut member = table::parse_member_fields(&row.cells, &columns, fields, date_format)
+                .map_err(parse_failed("a members table row"))?;
+            member.avatar_url = row.image;
+
+            yield member;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use fantoccini::error::CmdError;
+    use futures::{stream, Stream, TryStreamExt};
+    use scraper::{ElementRef, Html, Selector};
+    use url::Url;
+
+    use crate::client::SumsClientMembersError;
+    use crate::member::{Member, MemberFields, MemberType};
+    use crate::money::Money;
+
+    use super::{
+        resolve_image, scrape_members, table, table_cells, Browser, RowParts, TableRow,
+        MEMBERS_TABLE_ID,
+    };
+
+    /// A saved copy of a members table, trimmed down to a few made up members.
+    const MEMBERS_FIXTURE: &str = include_str!("fixtures/members.html");
+
+    /// Where the fixtures pretend to have been loaded from.
+    const FIXTURE_URL: &str = "https://student-dashboard.sums.su/groups/213/members";
+
+    /// The group the fixtures pretend to be the members table of.
+    const FIXTURE_GROUP_ID: u16 = 213;
+
+    /// A [`Browser`] that serves canned HTML instead of driving a real one.
+    struct HtmlFixture(Html);
+
+    impl HtmlFixture {
+        fn new(html: &str) -> Self {
+            Self(Html::parse_document(html))
+        }
+
+        fn select<'a>(&'a self, selector: &str) -> Vec<ElementRef<'a>> {
+            self.0.select(&Selector::parse(selector).unwrap()).collect()
+        }
+    }
+
+    /// The text of an element, tidied up like WebDriver's rendered text.
+    fn text(element: &ElementRef<'_>) -> String {
+        table::normalize_cell(&element.text().collect::<String>())
+    }
+
+    impl Browser for HtmlFixture {
+        // The fixture is already the members table, showing every member
+        async fn open_members_table(&self, _group_id: u16) -> Result<(), SumsClientMembersError> {
+            Ok(())
+        }
+
+        async fn show_all_table_entries(
+            &self,
+            _table_id: &str,
+        ) -> Result<(), SumsClientMembersError> {
+            Ok(())
+        }
+
+        async fn table_info_text(&self, table_id: &str) -> Result<String, CmdError> {
+            self.select(&format!("#{}_info", table_id))
+                .first()
+                .map(text)
+                .ok_or_else(|| CmdError::NotJson(format!("no info under table {}", table_id)))
+        }
+
+        async fn table_headers(&self, table_id: &str) -> Result<Vec<String>, CmdError> {
+            Ok(self
+                .select(&format!("#{} > thead th", table_id))
+                .iter()
+                .map(text)
+                .collect())
+        }
+
+        fn table_body_rows<'a>(
+            &'a self,
+            table_id: &'a str,
+            parts: &'a RowParts,
+        ) -> impl Stream<Item = Result<TableRow, CmdError>> + 'a {
+            let cell_selector = Selector::parse("td").unwrap();
+            let image_selector = Selector::parse("img").unwrap();
+            let page_url = Url::parse(FIXTURE_URL).unwrap();
+
+            let rows: Vec<_> = self
+                .select(&format!("#{} > tbody > tr", table_id))
+                .iter()
+                .map(|row| {
+                    Ok(TableRow {
+                        cells: row
+                            .select(&cell_selector)
+                            .enumerate()
+                            .map(|(i, cell)| {
+                                if parts.wants_cell(i) {
+                                    text(&cell)
+                                } else {
+                                    String::new()
+                                }
+                            })
+                            .collect(),
+                        image: row
+                            .select(&image_selector)
+                            .next()
+                            .filter(|_| parts.image)
+                            .and_then(|image| image.value().attr("src"))
+                            .and_then(|src| resolve_image(&page_url, src)),
+                    })
+                })
+                .collect();
+
+            stream::iter(rows)
+        }
+    }
+
+    /// Scrapes the members out of `html` the same way as
+    /// [`SumsClient::members`](crate::client::SumsClient::members).
+    async fn members(html: &str) -> Result<Vec<Member>, SumsClientMembersError> {
+        members_with_fields(html, MemberFields::ALL).await
+    }
+
+    async fn members_with_fields(
+        html: &str,
+        fields: MemberFields,
+    ) -> Result<Vec<Member>, SumsClientMembersError> {
+        scrape(html, fields, false).await
+    }
+
+    async fn scrape(
+        html: &str,
+        fields: MemberFields,
+        strict: bool,
+    ) -> Result<Vec<Member>, SumsClientMembersError> {
+        scrape_members(
+            &HtmlFixture::new(html),
+            FIXTURE_GROUP_ID,
+            fields,
+            None,
+            strict,
+        )
+        .try_collect()
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_members_from_fixture() {
+        let members = members(MEMBERS_FIXTURE).await.unwrap();
+
+        assert_eq!(members.len(), 3);
+
+        assert_eq!(members[0].student_id, "20123456");
+        assert_eq!(members[0].name, "Jane Doe");
+        assert_eq!(members[0].member_type, MemberType::Student);
+        assert_eq!(
+            members[0].subscription_purchased.as_ref().unwrap().price,
+            Money::from_pennies(500)
+        );
+        assert_eq!(
+            members[0].date_joined,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()
+        );
+
+        assert_eq!(members[1].name, "Doe, John");
+        assert_eq!(members[1].subscription_purchased, None);
+
+        assert_eq!(members[2].member_type, MemberType::LifeMember);
+    }
+
+    #[tokio::test]
+    async fn test_members_with_fields() {
+        let fields = MemberFields {
+            name: true,
+            ..MemberFields::NONE
+        };
+        let members = members_with_fields(MEMBERS_FIXTURE, fields).await.unwrap();
+
+        assert_eq!(members.len(), 3);
+        assert_eq!(members[0].student_id, "20123456");
+        assert_eq!(members[0].name, "Jane Doe");
+        assert_eq!(members[0].subscription_purchased, None);
+        assert_eq!(members[0].date_joined, NaiveDate::default());
+        assert_eq!(members[2].member_type, MemberType::Student);
+    }
+
+    #[tokio::test]
+    async fn test_member_avatars() {
+        let members = members(
+            r#"<table id="group-member-list-datatable">
+                <thead><tr>
+                    <th>Student ID</th><th>Name</th><th>Type</th><th>Subscription</th><th>Date Joined</th>
+                </tr></thead>
+                <tbody>
+                    <tr>
+                        <td>20123456</td>
+                        <td><img src="/storage/avatars/20123456.jpg"> Jane Doe</td>
+                        <td>Student</td><td></td><td>2023-10-01</td>
+                    </tr>
+                    <tr>
+                        <td>20654321</td><td>John Doe</td><td>Student</td><td></td><td>2023-10-01</td>
+                    </tr>
+                    <tr>
+                        <td>20111111</td>
+                        <td><img src=""> Alex Doe</td>
+                        <td>Student</td><td></td><td>2023-10-01</td>
+                    </tr>
+                </tbody>
+            </table>"#,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            members[0].avatar_url.as_ref().map(Url::as_str),
+            Some("https://student-dashboard.sums.su/storage/avatars/20123456.jpg")
+        );
+        assert_eq!(members[0].name, "Jane Doe");
+        assert_eq!(members[1].avatar_url, None);
+        assert_eq!(members[2].avatar_url, None);
+    }
+
+    #[tokio::test]
+    async fn test_empty_members_table() {
+        let members = members(
+            r#"<table id="group-member-list-datatable">
+                <thead><tr>
+                    <th>Student ID</th><th>Name</th><th>Type</th><th>Subscription</th><th>Date Joined</th>
+                </tr></thead>
+                <tbody><tr><td colspan="5">No data available in table</td></tr></tbody>
+            </table>"#,
+        )
+        .await
+        .unwrap();
+
+        assert!(members.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_table_cells() {
+        let fixture = HtmlFixture::new(
+            r#"<table id="group-event-list-datatable">
+                <thead><tr><th>Event</th><th>Date</th></tr></thead>
+                <tbody>
+                    <tr><td>Welcome
+                        Social</td><td>2023-10-01</td></tr>
+                    <tr><td>Hackathon</td><td>2023-11-18</td></tr>
+                </tbody>
+            </table>"#,
+        );
+
+        assert_eq!(
+            table_cells(&fixture, "group-event-list-datatable")
+                .await
+                .unwrap(),
+            [
+                ["Welcome Social", "2023-10-01"],
+                ["Hackathon", "2023-11-18"]
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_table_cells_empty() {
+        let fixture = HtmlFixture::new(
+            r#"<table id="group-event-list-datatable">
+                <thead><tr><th>Event</th><th>Date</th></tr></thead>
+                <tbody><tr><td colspan="2">No data available in table</td></tr></tbody>
+            </table>"#,
+        );
+
+        assert!(table_cells(&fixture, "group-event-list-datatable")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_members_table_missing_column() {
+        let result = members(
+            r#"<table id="group-member-list-datatable">
+                <thead><tr><th>Student ID</th><th>Name</th></tr></thead>
+                <tbody><tr><td>20123456</td><td>Jane Doe</td></tr></tbody>
+            </table>"#,
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(SumsClientMembersError::ParseFailed { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_members_skips_duplicates() {
+        let members = scrape(
+            r#"<table id="group-member-list-datatable">
+                <thead><tr>
+                    <th>Student ID</th><th>Name</th><th>Type</th><th>Subscription</th><th>Date Joined</th>
+                </tr></thead>
+                <tbody>
+                    <tr><td>20123456</td><td>Jane Doe</td><td>Student</td><td></td><td>2023-10-01</td></tr>
+                    <tr><td>20123456</td><td>Jane Doe</td><td>Student</td><td></td><td>2023-10-01</td></tr>
+                    <tr><td>20654321</td><td>John Doe</td><td>Student</td><td></td><td>2023-10-01</td></tr>
+                </tbody>
+            </table>
+            <div id="group-member-list-datatable_info">Showing 1 to 2 of 2 entries</div>"#,
+            MemberFields::ALL,
+            true,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].student_id, "20123456");
+        assert_eq!(members[1].student_id, "20654321");
+    }
+
+    #[tokio::test]
+    async fn test_members_count_mismatch() {
+        let html = MEMBERS_FIXTURE.replace("of 3 entries", "of 5 entries");
+
+        assert_eq!(
+            scrape(&html, MemberFields::ALL, false).await.unwrap().len(),
+            3
+        );
+        assert!(matches!(
+            scrape(&html, MemberFields::ALL, true).await,
+            Err(SumsClientMembersError::MemberCountMismatch {
+                expected: 5,
+                found: 3
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_entries_info_from_fixture() {
+        let fixture = HtmlFixture::new(MEMBERS_FIXTURE);
+
+        assert_eq!(
+            fixture.table_info_text(MEMBERS_TABLE_ID).await.unwrap(),
+            "Showing 1 to 3 of 3 entries"
+        );
+    }
+}
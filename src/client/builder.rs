@@ -0,0 +1,484 @@
+use std::time::Duration;
+
+use fantoccini::{error::NewSessionError, wd::Capabilities, Client, ClientBuilder};
+use serde_json::{json, Value};
+use url::Url;
+
+use super::{Selectors, SumsClient, SumsClientNewError};
+
+/// The browser requested when no capabilities are given. Chromium is used
+/// since geckodriver can't run multiple sessions simultaneously.
+const DEFAULT_BROWSER: &str = "chromium";
+
+/// The Chrome flags used by [`SumsClient::new_headless`].
+const HEADLESS_CHROME_ARGS: [&str; 3] = ["--headless=new", "--disable-gpu", "--no-sandbox"];
+
+/// The capabilities requested when none are given.
+pub(super) fn default_capabilities() -> Capabilities {
+    let mut capabilities = Capabilities::new();
+    capabilities.insert("browserName".to_string(), DEFAULT_BROWSER.into());
+    capabilities
+}
+
+/// The default capabilities, but with Chrome running headless.
+pub(super) fn headless_capabilities() -> Capabilities {
+    let mut capabilities = default_capabilities();
+    add_chrome_args(&mut capabilities, HEADLESS_CHROME_ARGS);
+    capabilities
+}
+
+/// Adds command line flags to `goog:chromeOptions`, keeping any that are
+/// already there.
+pub(super) fn add_chrome_args<I, S>(capabilities: &mut Capabilities, new_args: I)
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let chrome_options = capabilities
+        .entry("goog:chromeOptions")
+        .or_insert_with(|| json!({}));
+    if !chrome_options.is_object() {
+        *chrome_options = json!({});
+    }
+
+    let args = chrome_options
+        .as_object_mut()
+        .expect("chromeOptions was just made an object")
+        .entry("args")
+        .or_insert_with(|| json!([]));
+    if !args.is_array() {
+        *args = json!([]);
+    }
+
+    args.as_array_mut()
+        .expect("args was just made an array")
+        .extend(new_args.into_iter().map(|arg| Value::String(arg.into())));
+}
+
+/// Which TLS implementation to use for connecting to the WebDriver server,
+/// for servers behind `https://` addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TlsBackend {
+    /// rustls, with Mozilla's root certificates.
+    #[default]
+    Rustls,
+    /// The system's TLS library and certificate store. This is useful behind
+    /// proxies with internal CA certificates that rustls won't trust. Needs
+    /// the `native-tls` feature.
+    #[cfg(feature = "native-tls")]
+    NativeTls,
+}
+
+/// When the browser considers a page loaded, and so when navigating to it
+/// returns. This is WebDriver's `pageLoadStrategy` capability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PageLoadStrategy {
+    /// Wait for the whole page to load, including images and scripts like
+    /// analytics.
+    #[default]
+    Normal,
+    /// Only wait for the HTML to be parsed, which is usually all SUMS's
+    /// pages need before we can start looking for elements.
+    Eager,
+    /// Don't wait at all.
+    None,
+}
+
+impl PageLoadStrategy {
+    /// The strategy's name in WebDriver capabilities.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Eager => "eager",
+            Self::None => "none",
+        }
+    }
+}
+
+/// Everything needed to connect to the WebDriver server, kept by the client so
+/// that it can start a new session the same way if the first one dies.
+#[derive(Debug, Clone)]
+pub(crate) struct Connection {
+    webdriver_address: String,
+    capabilities: Capabilities,
+    tls_backend: TlsBackend,
+}
+
+impl Connection {
+    /// Starts a new WebDriver session.
+    pub(crate) async fn connect(&self) -> Result<Client, NewSessionError> {
+        // The builders for each backend are different types, so they can't
+        // share a variable
+        match self.tls_backend {
+            TlsBackend::Rustls => {
+                ClientBuilder::rustls()
+                    .capabilities(self.capabilities.clone())
+                    .connect(&self.webdriver_address)
+                    .await
+            }
+            #[cfg(feature = "native-tls")]
+            TlsBackend::NativeTls => {
+                ClientBuilder::native()
+                    .capabilities(self.capabilities.clone())
+                    .connect(&self.webdriver_address)
+                    .await
+            }
+        }
+    }
+}
+
+/// A builder for configuring and connecting a [`SumsClient`].
+///
+/// `group_id` and `webdriver_address` are required, everything else has a
+/// sensible default.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), libsums::client::SumsClientNewError> {
+/// use std::time::Duration;
+///
+/// use libsums::client::SumsClientBuilder;
+///
+/// let client = SumsClientBuilder::new()
+///     .group_id(213)
+///     .webdriver_address("http://localhost:9515")
+///     .timeout(Duration::from_secs(60))
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SumsClientBuilder {
+    group_id: Option<u16>,
+    webdriver_address: Option<String>,
+    capabilities: Option<Capabilities>,
+    timeout: Option<Duration>,
+    auth_timeout: Option<Duration>,
+    max_retries: Option<u32>,
+    base_url: Option<Url>,
+    dashboard_url: Option<Url>,
+    login_url: Option<Url>,
+    min_interval: Option<Duration>,
+    date_format: Option<String>,
+    reuse_dashboard: Option<bool>,
+    capture_on_error: Option<bool>,
+    stay_signed_in: Option<bool>,
+    strict_count: Option<bool>,
+    auto_reconnect: Option<bool>,
+    selectors: Option<Selectors>,
+    tls_backend: Option<TlsBackend>,
+    user_agent: Option<String>,
+    page_load_strategy: Option<PageLoadStrategy>,
+}
+
+impl SumsClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of your group. You can find it by going to your group's
+    /// management page, and looking at the URL. Required.
+    pub fn group_id(mut self, group_id: u16) -> Self {
+        self.group_id = Some(group_id);
+        self
+    }
+
+    /// Sets the address of your webdriver server. Required.
+    pub fn webdriver_address<S: Into<String>>(mut self, webdriver_address: S) -> Self {
+        self.webdriver_address = Some(webdriver_address.into());
+        self
+    }
+
+    /// Sets the capabilities to request when creating the WebDriver session.
+    /// These are used verbatim, so must include `browserName` if your WebDriver
+    /// server requires it. Defaults to just requesting chromium.
+    pub fn capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = Some(capabilities);
+        self
+    }
+
+    /// See [`SumsClient::with_timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// See [`SumsClient::with_auth_timeout`].
+    pub fn auth_timeout(mut self, auth_timeout: Duration) -> Self {
+        self.auth_timeout = Some(auth_timeout);
+        self
+    }
+
+    /// See [`SumsClient::with_max_retries`].
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// See [`SumsClient::with_base_url`].
+    pub fn base_url(mut self, base_url: Url) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// See [`SumsClient::with_dashboard_url`].
+    pub fn dashboard_url(mut self, dashboard_url: Url) -> Self {
+        self.dashboard_url = Some(dashboard_url);
+        self
+    }
+
+    /// See [`SumsClient::with_login_url`].
+    pub fn login_url(mut self, login_url: Url) -> Self {
+        self.login_url = Some(login_url);
+        self
+    }
+
+    /// See [`SumsClient::with_min_interval`].
+    pub fn min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = Some(min_interval);
+        self
+    }
+
+    /// See [`SumsClient::with_date_format`].
+    pub fn date_format<S: Into<String>>(mut self, date_format: S) -> Self {
+        self.date_format = Some(date_format.into());
+        self
+    }
+
+    /// See [`SumsClient::with_reuse_dashboard`].
+    pub fn reuse_dashboard(mut self, reuse_dashboard: bool) -> Self {
+        self.reuse_dashboard = Some(reuse_dashboard);
+        self
+    }
+
+    /// See [`SumsClient::with_capture_on_error`].
+    pub fn capture_on_error(mut self, capture_on_error: bool) -> Self {
+        self.capture_on_error = Some(capture_on_error);
+        self
+    }
+
+    /// See [`SumsClient::with_stay_signed_in`].
+    pub fn stay_signed_in(mut self, stay_signed_in: bool) -> Self {
+        self.stay_signed_in = Some(stay_signed_in);
+        self
+    }
+
+    /// See [`SumsClient::with_strict_count`].
+    pub fn strict_count(mut self, strict_count: bool) -> Self {
+        self.strict_count = Some(strict_count);
+        self
+    }
+
+    /// See [`SumsClient::with_auto_reconnect`].
+    pub fn auto_reconnect(mut self, auto_reconnect: bool) -> Self {
+        self.auto_reconnect = Some(auto_reconnect);
+        self
+    }
+
+    /// See [`SumsClient::with_selectors`].
+    pub fn selectors(mut self, selectors: Selectors) -> Self {
+        self.selectors = Some(selectors);
+        self
+    }
+
+    /// Sets the TLS implementation used to connect to the WebDriver server.
+    /// Defaults to [`TlsBackend::Rustls`].
+    pub fn tls_backend(mut self, tls_backend: TlsBackend) -> Self {
+        self.tls_backend = Some(tls_backend);
+        self
+    }
+
+    /// Sets the User-Agent the browser sends, so that SUMS can tell your
+    /// scraper apart from everyone else's traffic. This is done with Chrome's
+    /// `--user-agent` flag, so it only works with Chromium based drivers, and
+    /// is added to whatever [`SumsClientBuilder::capabilities`] were given.
+    pub fn user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets when the browser considers a page loaded. [`PageLoadStrategy::Eager`]
+    /// makes navigation noticeably quicker, since it doesn't wait for things
+    /// like analytics scripts. Defaults to whatever the
+    /// [`SumsClientBuilder::capabilities`] say, which is normally
+    /// [`PageLoadStrategy::Normal`].
+    pub fn page_load_strategy(mut self, page_load_strategy: PageLoadStrategy) -> Self {
+        self.page_load_strategy = Some(page_load_strategy);
+        self
+    }
+
+    /// The capabilities to request for the session, with any options that
+    /// are set through capabilities applied.
+    fn session_capabilities(&self) -> Capabilities {
+        let mut capabilities = self
+            .capabilities
+            .clone()
+            .unwrap_or_else(default_capabilities);
+
+        if let Some(user_agent) = &self.user_agent {
+            add_chrome_args(&mut capabilities, [format!("--user-agent={}", user_agent)]);
+        }
+
+        if let Some(page_load_strategy) = self.page_load_strategy {
+            capabilities.insert(
+                "pageLoadStrategy".to_string(),
+                page_load_strategy.as_str().into(),
+            );
+        }
+
+        capabilities
+    }
+
+    /// Connects to the WebDriver server and creates the client.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a required field wasn't set, or
+    /// if the client fails to connect.
+    pub async fn build(self) -> Result<SumsClient, SumsClientNewError> {
+        let capabilities = self.session_capabilities();
+
+        let group_id = self
+            .group_id
+            .ok_or(SumsClientNewError::MissingField("group_id"))?;
+        let webdriver_address = self
+            .webdriver_address
+            .ok_or(SumsClientNewError::MissingField("webdriver_address"))?;
+
+        let connection = Connection {
+            webdriver_address,
+            capabilities,
+            tls_backend: self.tls_backend.unwrap_or_default(),
+        };
+        let client = connection.connect().await?;
+
+        let mut sums_client = SumsClient::from_client(client, group_id);
+        sums_client.connection = Some(connection);
+
+        if let Some(timeout) = self.timeout {
+            sums_client.timeout = timeout;
+        }
+        if let Some(auth_timeout) = self.auth_timeout {
+            sums_client.auth_timeout = auth_timeout;
+        }
+        if let Some(max_retries) = self.max_retries {
+            sums_client.max_retries = max_retries;
+        }
+        if let Some(base_url) = self.base_url {
+            sums_client.base_url = base_url;
+        }
+        if let Some(dashboard_url) = self.dashboard_url {
+            sums_client.dashboard_url = dashboard_url;
+        }
+        if let Some(login_url) = self.login_url {
+            sums_client.login_url = Some(login_url);
+        }
+        if let Some(min_interval) = self.min_interval {
+            sums_client.min_interval = min_interval;
+        }
+        if let Some(date_format) = self.date_format {
+            sums_client.date_format = Some(date_format);
+        }
+        if let Some(reuse_dashboard) = self.reuse_dashboard {
+            sums_client.reuse_dashboard = reuse_dashboard;
+        }
+        if let Some(capture_on_error) = self.capture_on_error {
+            sums_client.capture_on_error = capture_on_error;
+        }
+        if let Some(stay_signed_in) = self.stay_signed_in {
+            sums_client.stay_signed_in = stay_signed_in;
+        }
+        if let Some(strict_count) = self.strict_count {
+            sums_client.strict_count = strict_count;
+        }
+        if let Some(auto_reconnect) = self.auto_reconnect {
+            sums_client.auto_reconnect = auto_reconnect;
+        }
+        if let Some(selectors) = self.selectors {
+            sums_client.selectors = selectors;
+        }
+
+        Ok(sums_client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fantoccini::wd::Capabilities;
+    use serde_json::json;
+
+    use crate::client::SumsClientNewError;
+
+    use super::{add_chrome_args, headless_capabilities, PageLoadStrategy, SumsClientBuilder};
+
+    #[test]
+    fn test_headless_capabilities() {
+        let capabilities = headless_capabilities();
+
+        assert_eq!(capabilities["browserName"], "chromium");
+        assert_eq!(
+            capabilities["goog:chromeOptions"]["args"],
+            json!(["--headless=new", "--disable-gpu", "--no-sandbox"])
+        );
+    }
+
+    #[test]
+    fn test_add_chrome_args_keeps_existing() {
+        let mut capabilities = Capabilities::new();
+        capabilities.insert(
+            "goog:chromeOptions".to_string(),
+            json!({ "args": ["--incognito"], "binary": "/usr/bin/chromium" }),
+        );
+
+        add_chrome_args(&mut capabilities, ["--headless=new"]);
+
+        assert_eq!(
+            capabilities["goog:chromeOptions"],
+            json!({ "args": ["--incognito", "--headless=new"], "binary": "/usr/bin/chromium" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_missing_fields() {
+        let result = SumsClientBuilder::new()
+            .webdriver_address("http://localhost:9515")
+            .build()
+            .await;
+        assert!(matches!(
+            result,
+            Err(SumsClientNewError::MissingField("group_id"))
+        ));
+
+        let result = SumsClientBuilder::new().group_id(213).build().await;
+        assert!(matches!(
+            result,
+            Err(SumsClientNewError::MissingField("webdriver_address"))
+        ));
+    }
+
+    #[test]
+    fn test_user_agent() {
+        let capabilities = SumsClientBuilder::new()
+            .user_agent("libsums-test/1.0")
+            .session_capabilities();
+
+        assert_eq!(capabilities["browserName"], "chromium");
+        assert_eq!(
+            capabilities["goog:chromeOptions"]["args"],
+            json!(["--user-agent=libsums-test/1.0"])
+        );
+    }
+
+    #[test]
+    fn test_page_load_strategy() {
+        let capabilities = SumsClientBuilder::new().session_capabilities();
+        assert!(!capabilities.contains_key("pageLoadStrategy"));
+
+        let capabilities = SumsClientBuilder::new()
+            .page_load_strategy(PageLoadStrategy::Eager)
+            .session_capabilities();
+
+        assert_eq!(capabilities["browserName"], "chromium");
+        assert_eq!(capabilities["pageLoadStrategy"], "eager");
+    }
+}
@@ -0,0 +1,226 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use futures::future::{join_all, try_join_all};
+
+use crate::member::Member;
+
+use super::{
+    SumsClient, SumsClientAuthError, SumsClientBuilder, SumsClientError, SumsClientMembersError,
+    SumsClientNewError,
+};
+
+/// Several [`SumsClient`]s, each driving its own browser session, for
+/// scraping lots of groups in parallel.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use libsums::client::{SumsClient, SumsClientPool};
+///
+/// let builder = SumsClient::builder()
+///     .group_id(213)
+///     .webdriver_address("http://localhost:9515");
+///
+/// let pool = SumsClientPool::from_builder(builder, 4).await?;
+/// pool.authenticate_from_env().await?;
+///
+/// for (group_id, members) in pool.members_for_groups(&[213, 214, 215]).await {
+///     match members {
+///         Ok(members) => println!("group {}: {} members", group_id, members.len()),
+///         Err(err) => eprintln!("group {}: {}", group_id, err),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct SumsClientPool {
+    clients: Vec<SumsClient>,
+    max_concurrency: usize,
+}
+
+impl SumsClientPool {
+    /// Creates a pool from clients that have already been created. They
+    /// don't have to be logged in yet, see [`SumsClientPool::authenticate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `clients` is empty.
+    pub fn new(clients: Vec<SumsClient>) -> Self {
+        assert!(
+            !clients.is_empty(),
+            "a SumsClientPool needs at least one client"
+        );
+
+        Self {
+            max_concurrency: clients.len(),
+            clients,
+        }
+    }
+
+    /// Creates a pool of `size` clients, each with its own browser session,
+    /// all configured by `builder`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if any of the clients can't be
+    /// created. Any that were created are closed first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub async fn from_builder(
+        builder: SumsClientBuilder,
+        size: usize,
+    ) -> Result<Self, SumsClientNewError> {
+        assert!(size > 0, "a SumsClientPool needs at least one client");
+
+        let results = join_all((0..size).map(|_| builder.clone().build())).await;
+
+        let mut clients = Vec::with_capacity(size);
+        let mut error = None;
+
+        for result in results {
+            match result {
+                Ok(client) => clients.push(client),
+                Err(err) => error = error.or(Some(err)),
+            }
+        }
+
+        if let Some(err) = error {
+            for client in clients {
+                if let Err(err) = client.close().await {
+                    warn!("failed to close a pooled client: {}", err);
+                }
+            }
+
+            return Err(err);
+        }
+
+        Ok(Self::new(clients))
+    }
+
+    /// Sets the most browser sessions to use at once, which defaults to all
+    /// of them. This can't be more than the number of clients in the pool,
+    /// and is clamped to between 1 and that.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.clamp(1, self.clients.len());
+        self
+    }
+
+    /// The number of clients in the pool.
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Always false, since a pool has at least one client. This is just here
+    /// to go with [`SumsClientPool::len`].
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// The clients in the pool, for calling methods that don't have a pooled
+    /// version.
+    pub fn clients(&self) -> &[SumsClient] {
+        &self.clients
+    }
+
+    /// Logs every client in the pool in, at the same time. See
+    /// [`SumsClient::authenticate`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error from any of the clients.
+    pub async fn authenticate<S>(&self, username: S, password: S) -> Result<(), SumsClientAuthError>
+    where
+        S: AsRef<str>,
+    {
+        let (username, password) = (username.as_ref(), password.as_ref());
+
+        try_join_all(
+            self.clients
+                .iter()
+                .map(|client| client.authenticate(username, password)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Logs every client in the pool in with the credentials from the
+    /// environment. See [`SumsClient::authenticate_from_env`].
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error from any of the clients.
+    pub async fn authenticate_from_env(&self) -> Result<(), SumsClientAuthError> {
+        try_join_all(
+            self.clients
+                .iter()
+                .map(|client| client.authenticate_from_env()),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gets the members of each group in `group_ids`, sharing the groups out
+    /// between the pool's clients so that several are scraped at once.
+    ///
+    /// A group failing doesn't stop the others, so each group gets its own
+    /// result. Groups listed more than once are only scraped once.
+    pub async fn members_for_groups(
+        &self,
+        group_ids: &[u16],
+    ) -> HashMap<u16, Result<Vec<Member>, SumsClientMembersError>> {
+        let mut queue = group_ids.to_vec();
+        queue.sort_unstable();
+        queue.dedup();
+
+        // Each worker takes the next group whenever it's done with one, so a
+        // slow group doesn't hold up the rest
+        let queue = Mutex::new(queue.into_iter());
+
+        let workers = self
+            .clients
+            .iter()
+            .take(self.max_concurrency)
+            .map(|client| {
+                let queue = &queue;
+
+                async move {
+                    let mut results = Vec::new();
+
+                    loop {
+                        let next = queue.lock().unwrap().next();
+                        let Some(group_id) = next else {
+                            break;
+                        };
+
+                        let members = client.members_for(group_id).await;
+
+                        if let Err(err) = &members {
+                            warn!("failed to get the members of group {}: {}", group_id, err);
+                        }
+
+                        results.push((group_id, members));
+                    }
+
+                    results
+                }
+            });
+
+        join_all(workers).await.into_iter().flatten().collect()
+    }
+
+    /// Closes every client's browser session.
+    ///
+    /// # Errors
+    ///
+    /// This function will return the first error from closing a client, after
+    /// trying to close all of them.
+    pub async fn close(self) -> Result<(), SumsClientError> {
+        join_all(self.clients.into_iter().map(SumsClient::close))
+            .await
+            .into_iter()
+            .collect()
+    }
+}
@@ -0,0 +1,153 @@
+use fantoccini::Locator;
+
+/// A way of finding an element on the page, as one of the WebDriver locator
+/// strategies.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Selector {
+    /// A CSS selector, like `#userActions .user-name`.
+    Css(String),
+    /// An element's `id` attribute, without the `#`.
+    Id(String),
+    /// An XPath expression, like `//*[@id="userActions"]/ul/li[1]/a[1]`.
+    XPath(String),
+}
+
+impl Selector {
+    pub fn css<S: Into<String>>(selector: S) -> Self {
+        Self::Css(selector.into())
+    }
+
+    pub fn id<S: Into<String>>(id: S) -> Self {
+        Self::Id(id.into())
+    }
+
+    pub fn xpath<S: Into<String>>(xpath: S) -> Self {
+        Self::XPath(xpath.into())
+    }
+
+    /// The fantoccini locator for this selector.
+    pub fn locator(&self) -> Locator<'_> {
+        match self {
+            Self::Css(selector) => Locator::Css(selector),
+            Self::Id(id) => Locator::Id(id),
+            Self::XPath(xpath) => Locator::XPath(xpath),
+        }
+    }
+}
+
+/// The selectors used to find things on the SU website and UoN login pages
+/// while logging in, and on the student dashboard's members table, so that a
+/// broken one can be patched with
+/// [`SumsClient::with_selectors`](super::SumsClient::with_selectors) without
+/// waiting for a new release.
+///
+/// Override just the ones you need and keep the defaults for the rest:
+///
+/// ```
+/// use libsums::client::{Selector, Selectors};
+///
+/// let selectors = Selectors {
+///     login_form: Selector::css("form#login"),
+///     ..Selectors::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Selectors {
+    /// The button that accepts or closes the SU website's cookie banner,
+    /// which can cover the user menu.
+    pub cookie_banner_dismiss: Selector,
+    /// The button that opens the user menu in the top right of the SU
+    /// website.
+    pub user_menu: Selector,
+    /// The student login button in the user menu.
+    pub login_link: Selector,
+    /// The logout link in the user menu, which is only there when logged in.
+    pub logout_link: Selector,
+    /// The logged in user's name in the user menu.
+    pub user_display_name: Selector,
+    /// The student dashboard link in the user menu, which is only there when
+    /// logged in.
+    pub dashboard_link: Selector,
+    /// The UoN login form.
+    pub login_form: Selector,
+    /// The username box, inside the login form.
+    pub username_input: Selector,
+    /// The password box, inside the login form.
+    pub password_input: Selector,
+    /// The message the UoN login page shows when the login fails.
+    pub login_error: Selector,
+    /// Anything UoN SSO shows when it wants a second factor after the
+    /// password, either a Duo frame or a one-time code box.
+    pub mfa_prompt: Selector,
+    /// The box to type a one-time code into, if the MFA prompt has one.
+    pub mfa_code_input: Selector,
+    /// The "Stay signed in?" page UoN SSO sometimes shows after the password.
+    pub stay_signed_in_prompt: Selector,
+    /// The "Yes" button on the "Stay signed in?" page.
+    pub stay_signed_in_yes: Selector,
+    /// The "No" button on the "Stay signed in?" page.
+    pub stay_signed_in_no: Selector,
+    /// The button that goes to the next page of the members table.
+    pub members_next_page: Selector,
+    /// The members table's search box.
+    pub members_search: Selector,
+}
+
+impl Default for Selectors {
+    fn default() -> Self {
+        Self {
+            cookie_banner_dismiss: Selector::css(
+                "#onetrust-accept-btn-handler, #cookie-accept, .cookie-banner button, .cc-allow, .cc-dismiss",
+            ),
+            user_menu: Selector::id("userActionsInvoker"),
+            login_link: Selector::xpath("//*[@id=\"userActions\"]/ul/li[1]/a[1]"),
+            logout_link: Selector::css("#userActions a[href*=\"logout\"]"),
+            user_display_name: Selector::css("#userActions .user-name"),
+            dashboard_link: Selector::id("studentDashboardLink"),
+            login_form: Selector::xpath("/html/body/div/div/div/div[1]/form"),
+            username_input: Selector::id("username"),
+            password_input: Selector::id("password"),
+            login_error: Selector::xpath("/html/body/div/div/div/div[1]/section/p"),
+            mfa_prompt: Selector::css(
+                "iframe#duo_iframe, iframe[src*='duosecurity'], input[name='otc'], input[autocomplete='one-time-code']",
+            ),
+            mfa_code_input: Selector::css("input[name='otc'], input[autocomplete='one-time-code']"),
+            stay_signed_in_prompt: Selector::css("#KmsiCheckboxField, form[action*='kmsi']"),
+            stay_signed_in_yes: Selector::id("idSIButton9"),
+            stay_signed_in_no: Selector::id("idBtn_Back"),
+            members_next_page: Selector::id("group-member-list-datatable_next"),
+            members_search: Selector::css("#group-member-list-datatable_filter input"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fantoccini::Locator;
+
+    use super::{Selector, Selectors};
+
+    #[test]
+    fn test_selector_locator() {
+        assert_eq!(Selector::css("h1").locator(), Locator::Css("h1"));
+        assert_eq!(Selector::id("username").locator(), Locator::Id("username"));
+        assert_eq!(
+            Selector::xpath("//form").locator(),
+            Locator::XPath("//form")
+        );
+    }
+
+    #[test]
+    fn test_override_selectors() {
+        let selectors = Selectors {
+            login_form: Selector::css("form#login"),
+            ..Selectors::default()
+        };
+
+        assert_eq!(selectors.login_form.locator(), Locator::Css("form#login"));
+        assert_eq!(
+            selectors.username_input,
+            Selectors::default().username_input
+        );
+    }
+}
@@ -0,0 +1,1016 @@
+//! Parsing for SUMS's DataTables, separate from the WebDriver code that reads
+//! them so that it can be tested offline.
+
+use chrono::NaiveDate;
+use csv::StringRecord;
+
+use crate::event::Event;
+use crate::member::{CommitteeMember, Member, MemberFields, MemberType, StudentId, Subscription};
+use crate::money::Money;
+use crate::product::Product;
+use crate::transaction::Transaction;
+
+use super::SumsClientMembersError;
+
+/// How many columns of the members table are required.
+const MEMBER_COLUMNS: usize = 5;
+
+/// How many columns the committee table has.
+const COMMITTEE_COLUMNS: usize = 3;
+
+/// How many columns the events table has.
+const EVENT_COLUMNS: usize = 4;
+
+/// How many columns the products table has.
+const PRODUCT_COLUMNS: usize = 3;
+
+/// How many columns the sales report table has.
+const TRANSACTION_COLUMNS: usize = 4;
+
+/// The date formats SUMS's tables have been seen to use, which can change
+/// with the dashboard's locale settings. Some dates also have a time after
+/// them, which is ignored.
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%d/%m/%Y", "%d %B %Y"];
+
+/// Makes sure a row has the number of cells we expect, so that a change to the
+/// table's layout produces an error rather than a panic or garbage data.
+fn check_columns(cells: &[String], expected: usize) -> Result<(), SumsClientMembersError> {
+    if cells.len() == expected {
+        Ok(())
+    } else {
+        Err(SumsClientMembersError::UnexpectedTableLayout {
+            expected,
+            found: cells.len(),
+        })
+    }
+}
+
+/// Whether a row of cells is the placeholder DataTables shows when there's
+/// nothing to show, such as "No data available in table" for a group with no
+/// members, or "No matching records found" for a search with no results. The
+/// placeholder is a single cell spanning every column.
+pub(crate) fn is_empty_placeholder(cells: &[String]) -> bool {
+    cells.len() == 1
+}
+
+/// Tidies up the text of a cell, trimming it and collapsing each run of
+/// whitespace inside it to a single space. Names and subscriptions sometimes
+/// come out of SUMS wrapped over several lines or padded with tabs.
+pub(crate) fn normalize_cell(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Where each of the columns we know about is in the members table, worked
+/// out from the text of its headers so that SUMS adding or reordering columns
+/// doesn't silently mix them up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MemberColumns {
+    pub student_id: usize,
+    pub name: usize,
+    pub member_type: usize,
+    pub subscription_purchased: usize,
+    pub date_joined: usize,
+    /// Not every group's table has an email column.
+    pub email: Option<usize>,
+    /// Or a column for when subscriptions expire.
+    pub subscription_expiry: Option<usize>,
+    /// How many columns the table has in total, including ones we don't use.
+    pub count: usize,
+}
+
+impl MemberColumns {
+    /// Builds the column map from the text of each header, in order.
+    ///
+    /// Returns [`SumsClientMembersError::UnexpectedTableLayout`] if any of the
+    /// required columns are missing, where `expected` is the number of
+    /// required columns and `found` is how many of them there were.
+    pub fn from_headers(headers: &[String]) -> Result<Self, SumsClientMembersError> {
+        let find = |names: &[&str]| {
+            headers.iter().position(|header| {
+                let header = header.trim();
+                names.iter().any(|name| header.eq_ignore_ascii_case(name))
+            })
+        };
+
+        let student_id = find(&["Student ID", "Student Number", "ID"]);
+        let name = find(&["Name", "Full Name"]);
+        let member_type = find(&["Type", "Member Type", "Membership Type"]);
+        let subscription_purchased = find(&["Subscription", "Subscription Purchased", "Product"]);
+        let date_joined = find(&["Date Joined", "Joined", "Join Date"]);
+
+        match (
+            student_id,
+            name,
+            member_type,
+            subscription_purchased,
+            date_joined,
+        ) {
+            (
+                Some(student_id),
+                Some(name),
+                Some(member_type),
+                Some(subscription_purchased),
+                Some(date_joined),
+            ) => Ok(Self {
+                student_id,
+                name,
+                member_type,
+                subscription_purchased,
+                date_joined,
+                email: find(&["Email", "Email Address"]),
+                subscription_expiry: find(&["Expiry", "Expires", "Expiry Date"]),
+                count: headers.len(),
+            }),
+            required => {
+                let found = [required.0, required.1, required.2, required.3, required.4]
+                    .iter()
+                    .filter(|column| column.is_some())
+                    .count();
+
+                Err(SumsClientMembersError::UnexpectedTableLayout {
+                    expected: MEMBER_COLUMNS,
+                    found,
+                })
+            }
+        }
+    }
+
+    /// The indices of the columns [`parse_member_fields`] needs to read for
+    /// `fields`, in order. The student ID column is always needed.
+    pub fn indices_for(&self, fields: &MemberFields) -> Vec<usize> {
+        let mut indices = vec![self.student_id];
+
+        let wanted = [
+            (fields.name, Some(self.name)),
+            (fields.member_type, Some(self.member_type)),
+            (
+                fields.subscription_purchased,
+                Some(self.subscription_purchased),
+            ),
+            (fields.date_joined, Some(self.date_joined)),
+            (fields.email, self.email),
+            (fields.subscription_expiry, self.subscription_expiry),
+        ];
+
+        indices.extend(
+            wanted
+                .into_iter()
+                .filter_map(|(wanted, column)| column.filter(|_| wanted)),
+        );
+        indices.sort_unstable();
+        indices
+    }
+}
+
+/// Parses the text of each cell in a members table row into a [`Member`],
+/// using `columns` to find each field. Dates are parsed with `date_format`
+/// if it's given and matches, otherwise with any of the [`DATE_FORMATS`].
+pub(crate) fn parse_member_row(
+    cells: &[String],
+    columns: &MemberColumns,
+    date_format: Option<&str>,
+) -> Result<Member, SumsClientMembersError> {
+    parse_member_fields(cells, columns, &MemberFields::ALL, date_format)
+}
+
+/// Like [`parse_member_row`], but only parses the columns for `fields` (and
+/// the student ID), leaving the rest of the member empty as described on
+/// [`MemberFields`]. The cells for other columns aren't looked at, so they
+/// can be left blank rather than read.
+pub(crate) fn parse_member_fields(
+    cells: &[String],
+    columns: &MemberColumns,
+    fields: &MemberFields,
+    date_format: Option<&str>,
+) -> Result<Member, SumsClientMembersError> {
+    check_columns(cells, columns.count)?;
+
+    let email = columns
+        .email
+        .filter(|_| fields.email)
+        .map(|email| cells[email].trim())
+        .filter(|email| !email.is_empty())
+        .map(ToString::to_string);
+
+    let subscription_expiry = match columns
+        .subscription_expiry
+        .filter(|_| fields.subscription_expiry)
+        .map(|i| cells[i].trim())
+    {
+        Some(expiry) if !expiry.is_empty() => Some(parse_date(expiry, date_format)?),
+        _ => None,
+    };
+
+    let name = if fields.name {
+        cells[columns.name].clone()
+    } else {
+        String::new()
+    };
+
+    let member_type = if fields.member_type {
+        cells[columns.member_type].parse::<MemberType>()?
+    } else {
+        MemberType::Student
+    };
+
+    let subscription_purchased = if fields.subscription_purchased {
+        Subscription::parse(&cells[columns.subscription_purchased])?
+    } else {
+        None
+    };
+
+    let date_joined = if fields.date_joined {
+        parse_date(&cells[columns.date_joined], date_format)?
+    } else {
+        NaiveDate::default()
+    };
+
+    let mut member = Member::new(
+        StudentId::try_from(cells[columns.student_id].as_str())?,
+        name,
+        member_type,
+        subscription_purchased,
+        date_joined,
+    );
+    member.email = email;
+    member.subscription_expiry = subscription_expiry;
+
+    Ok(member)
+}
+
+/// Parses a CSV of members, like SUMS's members export, matching up its
+/// columns by name the same way as [`MemberColumns::from_headers`].
+pub(crate) fn parse_members_csv(csv: &str) -> Result<Vec<Member>, SumsClientMembersError> {
+    let mut reader = csv::Reader::from_reader(csv.as_bytes());
+    let headers: Vec<String> = reader.headers()?.iter().map(String::from).collect();
+
+    // Work the columns out once, rather than for every record
+    let columns = MemberColumns::from_headers(&headers)?;
+
+    reader
+        .records()
+        .map(|record| parse_csv_record(&record?, &columns))
+        .collect()
+}
+
+/// Parses one record of a members CSV, given where each column is. See
+/// [`Member::from_csv_record`].
+pub(crate) fn parse_csv_record(
+    record: &StringRecord,
+    columns: &MemberColumns,
+) -> Result<Member, SumsClientMembersError> {
+    let cells: Vec<String> = record.iter().map(String::from).collect();
+
+    parse_member_row(&cells, columns, None)
+}
+
+/// Picks the page size that shows the most rows from the values of a
+/// DataTable's page size options. DataTables uses `-1` for "All", which wins
+/// outright, otherwise it's the biggest number. Values that aren't numbers
+/// are ignored.
+pub(crate) fn largest_page_size(option_values: &[String]) -> Option<&str> {
+    option_values
+        .iter()
+        .filter_map(|value| Some((value.as_str(), value.trim().parse::<i64>().ok()?)))
+        .filter(|&(_, size)| size == -1 || size > 0)
+        .max_by_key(|&(_, size)| if size == -1 { i64::MAX } else { size })
+        .map(|(value, _)| value)
+}
+
+/// The contents of a DataTable's info text, which reads something like
+/// "Showing 1 to 10 of 57 entries", or "Showing 1 to 3 of 3 entries (filtered
+/// from 1,234 total entries)" if a search is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct EntriesInfo {
+    pub start: usize,
+    pub end: usize,
+    /// The number of entries matching the current search.
+    pub total: usize,
+    /// The number of entries before the search was applied, if there is one.
+    pub unfiltered_total: Option<usize>,
+}
+
+impl EntriesInfo {
+    /// Parses the info text, returning `None` if it isn't in the expected
+    /// format.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut numbers = text
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c: char| !c.is_ascii_digit()))
+            .filter(|word| !word.is_empty())
+            .map(|word| word.replace(',', "").parse::<usize>());
+
+        let start = numbers.next()?.ok()?;
+        let end = numbers.next()?.ok()?;
+        let total = numbers.next()?.ok()?;
+
+        let unfiltered_total = if text.contains("filtered from") {
+            Some(numbers.next()?.ok()?)
+        } else {
+            None
+        };
+
+        Some(Self {
+            start,
+            end,
+            total,
+            unfiltered_total,
+        })
+    }
+
+    /// The total number of entries in the table, ignoring any search.
+    pub fn member_count(&self) -> usize {
+        self.unfiltered_total.unwrap_or(self.total)
+    }
+}
+
+/// Parses the text of each cell in a committee table row, which has the role,
+/// then the holder's name, then their student ID. Returns `None` for vacant
+/// positions.
+pub(crate) fn parse_committee_row(
+    cells: &[String],
+) -> Result<Option<CommitteeMember>, SumsClientMembersError> {
+    check_columns(cells, COMMITTEE_COLUMNS)?;
+
+    let role = cells[0].trim();
+    let name = cells[1].trim();
+    let student_id = cells[2].trim();
+
+    if name.is_empty() || name.eq_ignore_ascii_case("vacant") {
+        return Ok(None);
+    }
+
+    let student_id = if student_id.is_empty() {
+        None
+    } else {
+        Some(StudentId::try_from(student_id)?)
+    };
+
+    Ok(Some(CommitteeMember::new(
+        name.to_string(),
+        role.to_string(),
+        student_id,
+    )))
+}
+
+/// Parses the text of each cell in an events table row, which has the title,
+/// date, location and number of tickets sold. The location and ticket cells
+/// are empty for events without them.
+pub(crate) fn parse_event_row(
+    cells: &[String],
+    date_format: Option<&str>,
+) -> Result<Event, SumsClientMembersError> {
+    check_columns(cells, EVENT_COLUMNS)?;
+
+    let location = cells[2].trim();
+    let location = (!location.is_empty()).then(|| location.to_string());
+
+    // Ticketed events may show "sold / available", so only take the first
+    // number
+    let tickets_sold = match cells[3].split('/').next().unwrap_or("").trim() {
+        "" => None,
+        tickets_sold => Some(
+            tickets_sold
+                .replace(',', "")
+                .parse::<u32>()
+                .map_err(|_| SumsClientMembersError::InvalidTickets(tickets_sold.to_string()))?,
+        ),
+    };
+
+    Ok(Event::new(
+        cells[0].trim().to_string(),
+        parse_date(&cells[1], date_format)?,
+        location,
+        tickets_sold,
+    ))
+}
+
+/// Parses the text of each cell in a products table row, which has the name,
+/// price and whether the product is on sale.
+pub(crate) fn parse_product_row(cells: &[String]) -> Result<Product, SumsClientMembersError> {
+    check_columns(cells, PRODUCT_COLUMNS)?;
+
+    let price = cells[1].trim();
+    let price = price
+        .parse::<Money>()
+        .map_err(|_| SumsClientMembersError::InvalidPrice(price.to_string()))?;
+
+    let available = match cells[2].trim().to_ascii_lowercase().as_str() {
+        "available" | "on sale" | "in stock" | "yes" => true,
+        "unavailable" | "sold out" | "out of stock" | "no" => false,
+        _ => {
+            return Err(SumsClientMembersError::UnknownProductAvailability(
+                cells[2].trim().to_string(),
+            ))
+        }
+    };
+
+    Ok(Product::new(cells[0].trim().to_string(), price, available))
+}
+
+/// Parses the text of each cell in a sales report row, which has the date,
+/// the buyer's student ID, the product and the amount paid. The student ID
+/// cell is empty for buyers who aren't students. Dates are in `date_format`
+/// if it's given.
+pub(crate) fn parse_transaction_row(
+    cells: &[String],
+    date_format: Option<&str>,
+) -> Result<Transaction, SumsClientMembersError> {
+    check_columns(cells, TRANSACTION_COLUMNS)?;
+
+    let member = match cells[1].trim() {
+        "" => None,
+        student_id => Some(StudentId::try_from(student_id)?),
+    };
+
+    let amount = cells[3].trim();
+    let amount = amount
+        .parse::<Money>()
+        .map_err(|_| SumsClientMembersError::InvalidPrice(amount.to_string()))?;
+
+    Ok(Transaction::new(
+        member,
+        cells[2].trim().to_string(),
+        amount,
+        parse_date(&cells[0], date_format)?,
+    ))
+}
+
+/// Parses a date in `date_format` if it's given, or any of the
+/// [`DATE_FORMATS`], trying each in turn. If none of them work, the error is
+/// from the first format tried, with the text that couldn't be parsed.
+fn parse_date(text: &str, date_format: Option<&str>) -> Result<NaiveDate, SumsClientMembersError> {
+    let text = text.trim();
+    let mut first_error = None;
+
+    for format in date_format.into_iter().chain(DATE_FORMATS) {
+        // parse_and_remainder lets us ignore a time after the date
+        match NaiveDate::parse_and_remainder(text, format) {
+            Ok((date, _)) => return Ok(date),
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    Err(SumsClientMembersError::ChronoParseError {
+        source: first_error.expect("DATE_FORMATS isn't empty"),
+        value: text.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::{
+        client::SumsClientMembersError,
+        member::{MemberFields, MemberType, Subscription},
+        money::Money,
+    };
+
+    use super::{
+        is_empty_placeholder, largest_page_size, normalize_cell, parse_committee_row,
+        parse_event_row, parse_member_fields, parse_member_row, parse_members_csv,
+        parse_product_row, parse_transaction_row, EntriesInfo, MemberColumns,
+    };
+
+    /// Turns a row of `&str`s into the owned cell text the parser expects.
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(ToString::to_string).collect()
+    }
+
+    /// The headers of the members table as SUMS usually shows it.
+    fn member_headers() -> Vec<String> {
+        row(&["Student ID", "Name", "Type", "Subscription", "Date Joined"])
+    }
+
+    fn member_columns() -> MemberColumns {
+        MemberColumns::from_headers(&member_headers()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_member_fields() {
+        let fields = MemberFields {
+            name: true,
+            ..MemberFields::NONE
+        };
+        let columns = member_columns();
+
+        assert_eq!(columns.indices_for(&fields), [0, 1]);
+
+        // The other cells weren't read, so they're blank
+        let member = parse_member_fields(
+            &row(&["20123456", "Jane Doe", "", "", ""]),
+            &columns,
+            &fields,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(member.student_id, "20123456");
+        assert_eq!(member.name, "Jane Doe");
+        assert_eq!(member.member_type, MemberType::Student);
+        assert_eq!(member.subscription_purchased, None);
+        assert_eq!(member.date_joined, NaiveDate::default());
+
+        assert_eq!(columns.indices_for(&MemberFields::ALL), [0, 1, 2, 3, 4]);
+        assert_eq!(columns.indices_for(&MemberFields::NONE), [0]);
+    }
+
+    #[test]
+    fn test_parse_member_row() {
+        let member = parse_member_row(
+            &row(&[
+                "20123456",
+                "Jane Doe",
+                "Student",
+                "Standard Membership - £5.00",
+                "2023-10-01",
+            ]),
+            &member_columns(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(member.student_id, "20123456");
+        assert_eq!(member.name, "Jane Doe");
+        assert_eq!(member.member_type, MemberType::Student);
+        assert_eq!(
+            member.subscription_purchased,
+            Some(Subscription::new(
+                "Standard Membership".to_string(),
+                Money::from_pennies(500)
+            ))
+        );
+        assert_eq!(
+            member.date_joined,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()
+        );
+        assert_eq!(member.email, None);
+    }
+
+    #[test]
+    fn test_parse_member_row_invalid_date() {
+        let result = parse_member_row(
+            &row(&["20123456", "Jane Doe", "Student", "", "sometime in October"]),
+            &member_columns(),
+            None,
+        );
+
+        assert!(matches!(
+            result,
+            Err(SumsClientMembersError::ChronoParseError { value, .. }) if value == "sometime in October"
+        ));
+    }
+
+    #[test]
+    fn test_parse_member_row_date_formats() {
+        let date_joined = |date: &str, date_format: Option<&str>| {
+            parse_member_row(
+                &row(&["20123456", "Jane Doe", "Student", "", date]),
+                &member_columns(),
+                date_format,
+            )
+            .unwrap()
+            .date_joined
+        };
+        let first_of_october = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+
+        assert_eq!(date_joined("01/10/2023", None), first_of_october);
+        assert_eq!(date_joined("1 October 2023", None), first_of_october);
+        assert_eq!(date_joined("2023-10-01 12:34", None), first_of_october);
+
+        // A custom format takes priority over the known ones
+        assert_eq!(
+            date_joined("10/01/2023", Some("%m/%d/%Y")),
+            first_of_october
+        );
+        assert_eq!(
+            date_joined("10/01/2023", None),
+            NaiveDate::from_ymd_opt(2023, 1, 10).unwrap()
+        );
+
+        // but they're still tried if it doesn't match
+        assert_eq!(
+            date_joined("2023-10-01", Some("%m/%d/%Y")),
+            first_of_october
+        );
+    }
+
+    #[test]
+    fn test_parse_members_csv() {
+        let members = parse_members_csv(
+            "Name,Student ID,Email,Type,Subscription,Date Joined\n\
+             Jane Doe,20123456,jane.doe@nottingham.ac.uk,Student,Standard Membership - £5.00,2023-10-01\n\
+             \"Doe, John\",20654321,,Associate,,2023-10-02\n",
+        )
+        .unwrap();
+
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].student_id, "20123456");
+        assert_eq!(members[0].name, "Jane Doe");
+        assert_eq!(
+            members[0].email.as_deref(),
+            Some("jane.doe@nottingham.ac.uk")
+        );
+        assert_eq!(members[1].name, "Doe, John");
+        assert_eq!(members[1].member_type, MemberType::Associate);
+        assert_eq!(members[1].subscription_purchased, None);
+    }
+
+    #[test]
+    fn test_parse_members_csv_missing_column() {
+        assert!(matches!(
+            parse_members_csv("Student ID,Name\n20123456,Jane Doe\n"),
+            Err(SumsClientMembersError::UnexpectedTableLayout { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_member_row_with_email() {
+        let columns = MemberColumns::from_headers(&row(&[
+            "Student ID",
+            "Name",
+            "Email",
+            "Type",
+            "Subscription",
+            "Date Joined",
+        ]))
+        .unwrap();
+        assert_eq!(columns.email, Some(2));
+
+        let member = parse_member_row(
+            &row(&[
+                "20123456",
+                "Jane Doe",
+                "jane.doe@nottingham.ac.uk",
+                "Student",
+                "",
+                "2023-10-01",
+            ]),
+            &columns,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(member.name, "Jane Doe");
+        assert_eq!(member.email.as_deref(), Some("jane.doe@nottingham.ac.uk"));
+        assert_eq!(member.member_type, MemberType::Student);
+
+        let member = parse_member_row(
+            &row(&["20123456", "Jane Doe", "", "Student", "", "2023-10-01"]),
+            &columns,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(member.email, None);
+    }
+
+    #[test]
+    fn test_parse_member_row_with_expiry() {
+        let columns = MemberColumns::from_headers(&row(&[
+            "Student ID",
+            "Name",
+            "Type",
+            "Subscription",
+            "Date Joined",
+            "Expiry",
+        ]))
+        .unwrap();
+
+        let member = parse_member_row(
+            &row(&[
+                "20123456",
+                "Jane Doe",
+                "Student",
+                "Standard Membership - £5.00",
+                "2023-10-01",
+                "2024-08-31",
+            ]),
+            &columns,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            member.subscription_expiry,
+            NaiveDate::from_ymd_opt(2024, 8, 31)
+        );
+
+        let member = parse_member_row(
+            &row(&["20123456", "Jane Doe", "Student", "", "2023-10-01", ""]),
+            &columns,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(member.subscription_expiry, None);
+    }
+
+    #[test]
+    fn test_parse_reordered_member_row() {
+        let columns = MemberColumns::from_headers(&row(&[
+            "Date Joined",
+            "Actions",
+            "Name",
+            "Subscription",
+            "Student ID",
+            "Type",
+        ]))
+        .unwrap();
+
+        let member = parse_member_row(
+            &row(&["2023-10-01", "Edit", "Jane Doe", "", "20123456", "Student"]),
+            &columns,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(member.student_id, "20123456");
+        assert_eq!(member.name, "Jane Doe");
+        assert_eq!(
+            member.date_joined,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_member_columns_missing_header() {
+        let result = MemberColumns::from_headers(&row(&["Student ID", "Name", "Type", "Joined"]));
+
+        assert!(matches!(
+            result,
+            Err(SumsClientMembersError::UnexpectedTableLayout {
+                expected: 5,
+                found: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_member_row_wrong_column_count() {
+        let result = parse_member_row(
+            &row(&["20123456", "Jane Doe", "Student"]),
+            &member_columns(),
+            None,
+        );
+        assert!(matches!(
+            result,
+            Err(SumsClientMembersError::UnexpectedTableLayout {
+                expected: 5,
+                found: 3
+            })
+        ));
+
+        let result = parse_member_row(
+            &row(&[
+                "20123456",
+                "Jane Doe",
+                "Student",
+                "",
+                "2023-10-01",
+                "jane.doe@nottingham.ac.uk",
+            ]),
+            &member_columns(),
+            None,
+        );
+        assert!(matches!(
+            result,
+            Err(SumsClientMembersError::UnexpectedTableLayout {
+                expected: 5,
+                found: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_committee_row() {
+        let committee_member = parse_committee_row(&row(&["President", "Jane Doe", "20123456"]))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(committee_member.role, "President");
+        assert_eq!(committee_member.name, "Jane Doe");
+        assert_eq!(committee_member.student_id.unwrap(), "20123456");
+
+        let committee_member = parse_committee_row(&row(&["Staff Contact", "John Doe", ""]))
+            .unwrap()
+            .unwrap();
+        assert!(committee_member.student_id.is_none());
+    }
+
+    #[test]
+    fn test_parse_vacant_committee_row() {
+        assert!(parse_committee_row(&row(&["Treasurer", "", ""]))
+            .unwrap()
+            .is_none());
+        assert!(parse_committee_row(&row(&["Treasurer", "Vacant", ""]))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_empty_table_placeholder() {
+        assert!(is_empty_placeholder(&row(&["No data available in table"])));
+        assert!(is_empty_placeholder(&row(&["No matching records found"])));
+        assert!(!is_empty_placeholder(&row(&[
+            "20123456",
+            "Jane Doe",
+            "Student",
+            "",
+            "2023-10-01",
+        ])));
+    }
+
+    #[test]
+    fn test_normalize_cell() {
+        assert_eq!(normalize_cell("Jane Doe"), "Jane Doe");
+        assert_eq!(normalize_cell("  Jane\n\t\tDoe \n"), "Jane Doe");
+        assert_eq!(
+            normalize_cell("Standard Membership\n - \u{a0}£5.00"),
+            "Standard Membership - £5.00"
+        );
+        assert_eq!(normalize_cell(" \t\n"), "");
+    }
+
+    #[test]
+    fn test_largest_page_size() {
+        assert_eq!(
+            largest_page_size(&row(&["10", "25", "50", "100", "100000"])),
+            Some("100000")
+        );
+        assert_eq!(largest_page_size(&row(&["10", "-1", "100"])), Some("-1"));
+        assert_eq!(largest_page_size(&row(&["25", "", "All"])), Some("25"));
+        assert_eq!(largest_page_size(&row(&["0", "nope"])), None);
+        assert_eq!(largest_page_size(&[]), None);
+    }
+
+    #[test]
+    fn test_parse_entries_info() {
+        assert_eq!(
+            EntriesInfo::parse("Showing 1 to 10 of 57 entries"),
+            Some(EntriesInfo {
+                start: 1,
+                end: 10,
+                total: 57,
+                unfiltered_total: None,
+            })
+        );
+        assert_eq!(
+            EntriesInfo::parse("Showing 0 to 0 of 0 entries"),
+            Some(EntriesInfo {
+                start: 0,
+                end: 0,
+                total: 0,
+                unfiltered_total: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_filtered_entries_info() {
+        let info =
+            EntriesInfo::parse("Showing 1 to 3 of 3 entries (filtered from 1,234 total entries)")
+                .unwrap();
+
+        assert_eq!(info.total, 3);
+        assert_eq!(info.unfiltered_total, Some(1234));
+        assert_eq!(info.member_count(), 1234);
+    }
+
+    #[test]
+    fn test_parse_invalid_entries_info() {
+        assert_eq!(EntriesInfo::parse(""), None);
+        assert_eq!(EntriesInfo::parse("Showing entries"), None);
+    }
+
+    #[test]
+    fn test_parse_event_row() {
+        let event = parse_event_row(
+            &row(&[
+                "Intro to Rust",
+                "12/10/2023 18:00",
+                "Computer Science A32",
+                "25 / 40",
+            ]),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(event.title, "Intro to Rust");
+        assert_eq!(event.date, NaiveDate::from_ymd_opt(2023, 10, 12).unwrap());
+        assert_eq!(event.location.as_deref(), Some("Computer Science A32"));
+        assert_eq!(event.tickets_sold, Some(25));
+    }
+
+    #[test]
+    fn test_parse_unticketed_event_row() {
+        let event = parse_event_row(&row(&["Social", "2023-10-12", "", ""]), None).unwrap();
+
+        assert_eq!(event.location, None);
+        assert_eq!(event.tickets_sold, None);
+    }
+
+    #[test]
+    fn test_parse_event_row_invalid_date() {
+        assert!(matches!(
+            parse_event_row(&row(&["Social", "next Tuesday", "", ""]), None),
+            Err(SumsClientMembersError::ChronoParseError { value, .. }) if value == "next Tuesday"
+        ));
+    }
+
+    #[test]
+    fn test_parse_event_row_invalid_tickets() {
+        assert!(matches!(
+            parse_event_row(&row(&["Social", "2023-10-12", "", "lots / 40"]), None),
+            Err(SumsClientMembersError::InvalidTickets(value)) if value == "lots"
+        ));
+    }
+
+    #[test]
+    fn test_parse_event_row_date_format() {
+        let event =
+            parse_event_row(&row(&["Social", "10.12.2023", "", ""]), Some("%m.%d.%Y")).unwrap();
+
+        assert_eq!(event.date, NaiveDate::from_ymd_opt(2023, 10, 12).unwrap());
+    }
+
+    #[test]
+    fn test_parse_product_row() {
+        let product = parse_product_row(&row(&["HackSoc Hoodie", "£25.00", "Available"])).unwrap();
+
+        assert_eq!(product.name, "HackSoc Hoodie");
+        assert_eq!(product.price, Money::from_pennies(2500));
+        assert!(product.available);
+
+        let product = parse_product_row(&row(&["Sticker", "Free", "Sold Out"])).unwrap();
+
+        assert_eq!(product.price, Money::ZERO);
+        assert!(!product.available);
+    }
+
+    #[test]
+    fn test_parse_product_row_invalid() {
+        assert!(matches!(
+            parse_product_row(&row(&["Hoodie", "twenty quid", "Available"])),
+            Err(SumsClientMembersError::InvalidPrice(_))
+        ));
+        assert!(matches!(
+            parse_product_row(&row(&["Hoodie", "£25.00", "Maybe"])),
+            Err(SumsClientMembersError::UnknownProductAvailability(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_transaction_row() {
+        let transaction = parse_transaction_row(
+            &row(&[
+                "2023-10-01 14:32",
+                "20123456",
+                "Standard Membership",
+                "£5.00",
+            ]),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(transaction.member.unwrap(), "20123456");
+        assert_eq!(transaction.product, "Standard Membership");
+        assert_eq!(transaction.amount, Money::from_pennies(500));
+        assert_eq!(
+            transaction.date,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()
+        );
+
+        // Guests and refunds
+        let transaction = parse_transaction_row(
+            &row(&["02/10/2023", "", "Standard Membership", "-£5.00"]),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(transaction.member, None);
+        assert_eq!(transaction.amount, Money::from_pennies(-500));
+    }
+
+    #[test]
+    fn test_parse_transaction_row_invalid() {
+        assert!(matches!(
+            parse_transaction_row(&row(&["2023-10-01", "20123456", "Hoodie", "lots"]), None),
+            Err(SumsClientMembersError::InvalidPrice(_))
+        ));
+        assert!(matches!(
+            parse_transaction_row(&row(&["2023-10-01", "Jane Doe", "Hoodie", "£25.00"]), None),
+            Err(SumsClientMembersError::InvalidStudentId(_))
+        ));
+        assert!(matches!(
+            parse_transaction_row(&row(&["2023-10-01", "Hoodie", "£25.00"]), None),
+            Err(SumsClientMembersError::UnexpectedTableLayout {
+                expected: 4,
+                found: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use crate::member::{Member, StudentId};
+
+/// The differences between two lists of members, from [`diff_members`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemberDiff {
+    /// Members in the new list but not the old one, in the new list's order.
+    pub added: Vec<Member>,
+    /// Members in the old list but not the new one, in the old list's order.
+    pub removed: Vec<Member>,
+    /// Members in both lists whose details changed, as `(old, new)` pairs in
+    /// the new list's order.
+    pub changed: Vec<(Member, Member)>,
+}
+
+impl MemberDiff {
+    /// Whether the two lists had the same members with the same details.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compares an old and new list of members, matching them up by student ID.
+/// If a list has the same student ID more than once, only the first counts.
+pub fn diff_members(old: &[Member], new: &[Member]) -> MemberDiff {
+    let old_by_id = by_student_id(old);
+    let new_by_id = by_student_id(new);
+
+    let mut diff = MemberDiff::default();
+
+    for new_member in new {
+        if !is_first(&new_by_id, new_member) {
+            continue;
+        }
+
+        match old_by_id.get(&new_member.student_id) {
+            None => diff.added.push(new_member.clone()),
+            Some(old_member) if details_changed(old_member, new_member) => diff
+                .changed
+                .push(((*old_member).clone(), new_member.clone())),
+            Some(_) => {}
+        }
+    }
+
+    for old_member in old {
+        if is_first(&old_by_id, old_member) && !new_by_id.contains_key(&old_member.student_id) {
+            diff.removed.push(old_member.clone());
+        }
+    }
+
+    diff
+}
+
+/// Indexes members by student ID, keeping the first of any duplicates.
+fn by_student_id(members: &[Member]) -> HashMap<&StudentId, &Member> {
+    let mut by_id = HashMap::new();
+
+    for member in members {
+        by_id.entry(&member.student_id).or_insert(member);
+    }
+
+    by_id
+}
+
+/// Whether `member` is the one [`by_student_id`] kept for its student ID.
+fn is_first(by_id: &HashMap<&StudentId, &Member>, member: &Member) -> bool {
+    by_id
+        .get(&member.student_id)
+        .is_some_and(|first| std::ptr::eq(*first, member))
+}
+
+/// Whether anything other than the student ID differs between two members.
+/// This is spelled out rather than using `==` so that it keeps working if
+/// members are ever compared by student ID alone.
+fn details_changed(old: &Member, new: &Member) -> bool {
+    old.name != new.name
+        || old.member_type != new.member_type
+        || old.subscription_purchased != new.subscription_purchased
+        || old.date_joined != new.date_joined
+        || old.email != new.email
+        || old.subscription_expiry != new.subscription_expiry
+        || old.avatar_url != new.avatar_url
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::member::{Member, MemberType, StudentId, Subscription};
+    use crate::money::Money;
+
+    use super::diff_members;
+
+    fn member(student_id: &str, name: &str) -> Member {
+        Member::new(
+            StudentId::try_from(student_id).unwrap(),
+            name.to_string(),
+            MemberType::Student,
+            None,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_diff_added() {
+        let old = vec![member("20000001", "Jane Doe")];
+        let new = vec![
+            member("20000001", "Jane Doe"),
+            member("20000002", "John Doe"),
+        ];
+
+        let diff = diff_members(&old, &new);
+
+        assert_eq!(diff.added, [member("20000002", "John Doe")]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.changed.is_empty());
+    }
+
+    #[test]
+    fn test_diff_removed() {
+        let old = vec![
+            member("20000001", "Jane Doe"),
+            member("20000002", "John Doe"),
+        ];
+        let new = vec![member("20000002", "John Doe")];
+
+        let diff = diff_members(&old, &new);
+
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.removed, [member("20000001", "Jane Doe")]);
+        assert!(diff.changed.is_empty());
+    }
+
+    #[test]
+    fn test_diff_changed() {
+        let old = vec![
+            member("20000001", "Jane Doe"),
+            member("20000002", "John Doe"),
+        ];
+
+        let mut renamed = member("20000001", "Jane Smith");
+        renamed.subscription_purchased = Some(Subscription::new(
+            "Standard Membership".to_string(),
+            Money::from_pennies(500),
+        ));
+        let new = vec![renamed.clone(), member("20000002", "John Doe")];
+
+        let diff = diff_members(&old, &new);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        // Members are only compared by student ID, so compare everything
+        assert_eq!(
+            format!("{:?}", diff.changed),
+            format!("{:?}", [(member("20000001", "Jane Doe"), renamed)])
+        );
+    }
+
+    #[test]
+    fn test_diff_unchanged() {
+        let members = vec![
+            member("20000001", "Jane Doe"),
+            member("20000002", "John Doe"),
+        ];
+
+        assert!(diff_members(&members, &members).is_empty());
+    }
+
+    #[test]
+    fn test_diff_duplicates() {
+        let old = vec![member("20000001", "Jane Doe")];
+        let new = vec![
+            member("20000001", "Jane Doe"),
+            member("20000001", "Jane Smith"),
+        ];
+
+        // Only the first of the duplicates counts
+        assert!(diff_members(&old, &new).is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+use chrono::NaiveDate;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An event from a group's events page on the student dashboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Event {
+    pub title: String,
+    pub date: NaiveDate,
+    pub location: Option<String>,
+    /// How many tickets have been sold, if the event is ticketed.
+    pub tickets_sold: Option<u32>,
+}
+
+impl Event {
+    pub fn new(
+        title: String,
+        date: NaiveDate,
+        location: Option<String>,
+        tickets_sold: Option<u32>,
+    ) -> Self {
+        Self {
+            title,
+            date,
+            location,
+            tickets_sold,
+        }
+    }
+}
+
+/// Which events [`SumsClient::events`](crate::client::SumsClient::events)
+/// should return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventFilter {
+    /// Events happening today or later.
+    #[default]
+    Upcoming,
+    /// Events that happened before today.
+    Past,
+    All,
+}
+
+impl EventFilter {
+    /// Whether an event on `date` passes the filter, if it's currently
+    /// `today`.
+    pub fn matches(&self, date: NaiveDate, today: NaiveDate) -> bool {
+        match self {
+            Self::Upcoming => date >= today,
+            Self::Past => date < today,
+            Self::All => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::EventFilter;
+
+    #[test]
+    fn test_event_filter() {
+        let today = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+        let yesterday = NaiveDate::from_ymd_opt(2023, 9, 30).unwrap();
+
+        assert!(EventFilter::Upcoming.matches(today, today));
+        assert!(!EventFilter::Upcoming.matches(yesterday, today));
+        assert!(EventFilter::Past.matches(yesterday, today));
+        assert!(!EventFilter::Past.matches(today, today));
+        assert!(EventFilter::All.matches(yesterday, today));
+    }
+}
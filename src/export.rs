@@ -0,0 +1,195 @@
+use std::io::Write;
+
+use crate::member::Member;
+
+/// The header row written by [`to_csv`].
+const CSV_HEADER: [&str; 5] = [
+    "student_id",
+    "name",
+    "member_type",
+    "subscription_purchased",
+    "date_joined",
+];
+
+/// Writes members as CSV one at a time, in the format of [`to_csv`], for when
+/// they aren't all in memory at once.
+pub(crate) struct MemberCsvWriter<W: Write> {
+    writer: csv::Writer<W>,
+}
+
+impl<W: Write> MemberCsvWriter<W> {
+    /// Writes the header row to `writer`.
+    pub(crate) fn new(writer: W) -> Result<Self, csv::Error> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(CSV_HEADER)?;
+
+        Ok(Self { writer })
+    }
+
+    pub(crate) fn write(&mut self, member: &Member) -> Result<(), csv::Error> {
+        // Going through to_record keeps the two in sync
+        let record = member.to_record();
+
+        self.writer.write_record(CSV_HEADER.map(|key| &record[key]))
+    }
+
+    pub(crate) fn flush(&mut self) -> Result<(), csv::Error> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+/// Writes a list of members to `writer` as CSV, for things like opening the
+/// member list in a spreadsheet.
+///
+/// A header row is always written, even if `members` is empty. Dates are
+/// written in ISO-8601 format (`YYYY-MM-DD`).
+///
+/// # Errors
+///
+/// This function will return an error if writing to `writer` fails.
+pub fn to_csv<W: Write>(members: &[Member], writer: W) -> Result<(), csv::Error> {
+    let mut csv_writer = MemberCsvWriter::new(writer)?;
+
+    for member in members {
+        csv_writer.write(member)?;
+    }
+
+    csv_writer.flush()
+}
+
+/// Writes a list of members to `writer` as newline-delimited JSON, with one
+/// compact JSON object per member.
+///
+/// # Errors
+///
+/// This function will return an error if writing to `writer` fails.
+#[cfg(feature = "serde")]
+pub fn to_json_lines<W: Write>(members: &[Member], mut writer: W) -> Result<(), serde_json::Error> {
+    for member in members {
+        serde_json::to_writer(&mut writer, member)?;
+        writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+    }
+
+    writer.flush().map_err(serde_json::Error::io)
+}
+
+/// Writes a list of members to `writer` as a single pretty-printed JSON
+/// array.
+///
+/// # Errors
+///
+/// This function will return an error if writing to `writer` fails.
+#[cfg(feature = "serde")]
+pub fn to_json_array<W: Write>(members: &[Member], writer: W) -> Result<(), serde_json::Error> {
+    serde_json::to_writer_pretty(writer, members)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::member::{Member, MemberType, StudentId, Subscription};
+    use crate::money::Money;
+
+    use super::{to_csv, MemberCsvWriter};
+    #[cfg(feature = "serde")]
+    use super::{to_json_array, to_json_lines};
+
+    fn members() -> Vec<Member> {
+        vec![
+            Member::new(
+                StudentId::try_from("20123456").unwrap(),
+                "Jane Doe".to_string(),
+                MemberType::Student,
+                Some(Subscription::new(
+                    "Standard Membership".to_string(),
+                    Money::from_pennies(500),
+                )),
+                NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(),
+            ),
+            Member::new(
+                StudentId::try_from("20654321").unwrap(),
+                "Doe, John".to_string(),
+                MemberType::LifeMember,
+                None,
+                NaiveDate::from_ymd_opt(2021, 9, 24).unwrap(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let members = members();
+
+        let mut output = Vec::new();
+        to_csv(&members, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "student_id,name,member_type,subscription_purchased,date_joined\n\
+             20123456,Jane Doe,Student,Standard Membership - £5.00,2023-10-01\n\
+             20654321,\"Doe, John\",Life Member,,2021-09-24\n"
+        );
+    }
+
+    #[test]
+    fn test_to_csv_empty() {
+        let mut output = Vec::new();
+        to_csv(&[], &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "student_id,name,member_type,subscription_purchased,date_joined\n"
+        );
+    }
+
+    #[test]
+    fn test_member_csv_writer_flush() {
+        let members = members();
+
+        let mut output = Vec::new();
+        let mut csv_writer = MemberCsvWriter::new(&mut output).unwrap();
+        csv_writer.write(&members[0]).unwrap();
+        csv_writer.flush().unwrap();
+        drop(csv_writer);
+
+        // What was flushed is there even though the writer stopped early
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "student_id,name,member_type,subscription_purchased,date_joined\n\
+             20123456,Jane Doe,Student,Standard Membership - £5.00,2023-10-01\n"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json_lines() {
+        let members = members();
+
+        let mut output = Vec::new();
+        to_json_lines(&members, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), members.len());
+
+        for (line, member) in lines.iter().zip(&members) {
+            let deserialized = serde_json::from_str::<Member>(line).unwrap();
+            assert_eq!(format!("{:?}", deserialized), format!("{:?}", member));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json_array() {
+        let members = members();
+
+        let mut output = Vec::new();
+        to_json_array(&members, &mut output).unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<Vec<Member>>(&output).unwrap(),
+            members
+        );
+    }
+}
@@ -0,0 +1,13 @@
+/// Information about a SUMS group (i.e. a society).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupInfo {
+    pub id: u16,
+    pub name: String,
+}
+
+impl GroupInfo {
+    pub fn new(id: u16, name: String) -> Self {
+        Self { id, name }
+    }
+}
@@ -1,18 +1,27 @@
-// Adds an option to SUMS's "show x entries" to show 100000 entries. It should
-// be noted that SUMS's member dashboard doesn't actually paginate requests, but
-// just annoyingly hides entries after the fact.
+// Adds an option to a DataTable's "show x entries" to show 100000 entries. It
+// should be noted that SUMS's dashboard tables don't actually paginate
+// requests, but just annoyingly hide entries after the fact.
+//
+// The function is only defined once per page, and the option is only added if
+// it isn't already there, so this is safe to run more than once.
 
-// Returns the value of the new option.
-let newOptionValue = 100000;
+// Takes the ID of the table, and returns the value of the new option.
+if (typeof window.addShowAllEntries !== "function") {
+    window.addShowAllEntries = function (tableId) {
+        let newOptionValue = 100000;
 
-let selector = document.querySelector("#group-member-list-datatable_length > label:nth-child(1) > select:nth-child(1)");
+        let selector = document.querySelector("#" + tableId + "_length select");
 
-if (selector != null) {
-    let newOption = document.createElement("option");
-    newOption.setAttribute("value", newOptionValue);
-    newOption.innerHTML = newOptionValue;
+        if (selector != null && selector.querySelector("option[value='" + newOptionValue + "']") == null) {
+            let newOption = document.createElement("option");
+            newOption.setAttribute("value", newOptionValue);
+            newOption.innerHTML = newOptionValue;
 
-    selector.appendChild(newOption);
+            selector.appendChild(newOption);
+        }
+
+        return newOptionValue;
+    };
 }
 
-return newOptionValue;
\ No newline at end of file
+return window.addShowAllEntries(arguments[0]);
@@ -0,0 +1,22 @@
+// Downloads a URL with the page's cookies and calls back with the body as
+// text. WebDriver has no way of reading files the browser downloads, so this
+// is used instead of actually clicking on download links.
+//
+// If the download fails, it calls back with an object whose `error` says why
+// instead, since throwing here would just time the script out.
+
+// Takes the URL to download.
+let url = arguments[0];
+let done = arguments[arguments.length - 1];
+
+fetch(url, { credentials: "include" })
+    .then(function (response) {
+        if (!response.ok) {
+            throw new Error(response.status + " " + response.statusText);
+        }
+
+        return response.text();
+    })
+    .then(done, function (err) {
+        done({ error: String(err) });
+    });
@@ -1,2 +1,17 @@
+#[macro_use]
+mod trace;
+
+pub mod academic_year;
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
+pub mod diff;
+pub mod event;
+pub mod export;
+pub mod group;
 pub mod member;
+pub mod money;
+pub mod product;
+pub mod report;
+pub mod session;
+pub mod transaction;
@@ -1,31 +1,329 @@
-use chrono::NaiveDate;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
-pub type StudentId = String;
+use chrono::{Local, NaiveDate};
+use csv::StringRecord;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use url::Url;
 
-#[derive(Debug, Clone)]
+use crate::client::table::{self, MemberColumns};
+use crate::client::SumsClientMembersError;
+use crate::money::Money;
+
+/// How many digits UoN student IDs have.
+const STUDENT_ID_LENGTH: usize = 8;
+
+/// A UoN student ID. This is always 8 digits, and is stored as a string rather
+/// than an integer so that leading zeros are preserved.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct StudentId(String);
+
+#[derive(Debug, Error)]
+#[error("Invalid student ID {0:?}, expected {STUDENT_ID_LENGTH} digits")]
+pub struct StudentIdParseError(pub String);
+
+impl StudentId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<&str> for StudentId {
+    type Error = StudentIdParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+
+        if value.len() == STUDENT_ID_LENGTH && value.chars().all(|c| c.is_ascii_digit()) {
+            Ok(Self(value.to_string()))
+        } else {
+            Err(StudentIdParseError(value.to_string()))
+        }
+    }
+}
+
+impl TryFrom<String> for StudentId {
+    type Error = StudentIdParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+impl From<StudentId> for String {
+    fn from(value: StudentId) -> Self {
+        value.0
+    }
+}
+
+impl AsRef<str> for StudentId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for StudentId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for StudentId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl fmt::Display for StudentId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The type of a membership, as shown in the type column of SUMS's member
+/// list. The strings SUMS uses live in [`MemberType::as_str`] and its
+/// [`FromStr`] impl; everything else, including the serde form, goes through
+/// those.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
 pub enum MemberType {
     Student,
+    Associate,
+    Committee,
+    LifeMember,
+    Staff,
+}
+
+impl MemberType {
+    /// Every member type, in the order they're declared.
+    pub const ALL: [MemberType; 5] = [
+        Self::Student,
+        Self::Associate,
+        Self::Committee,
+        Self::LifeMember,
+        Self::Staff,
+    ];
+
+    /// The member type as SUMS writes it.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Student => "Student",
+            Self::Associate => "Associate",
+            Self::Committee => "Committee",
+            Self::LifeMember => "Life Member",
+            Self::Staff => "Staff",
+        }
+    }
+}
+
+impl fmt::Display for MemberType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Unknown member type {0:?}")]
+pub struct UnknownMemberTypeError(pub String);
+
+impl FromStr for MemberType {
+    type Err = UnknownMemberTypeError;
+
+    /// Parses a member type as it's shown in the type column of SUMS's member
+    /// list, ignoring surrounding whitespace.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+
+        Self::ALL
+            .into_iter()
+            .find(|member_type| member_type.as_str() == value)
+            .ok_or_else(|| UnknownMemberTypeError(value.to_string()))
+    }
+}
+
+impl TryFrom<&str> for MemberType {
+    type Error = UnknownMemberTypeError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for MemberType {
+    type Error = UnknownMemberTypeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<MemberType> for String {
+    fn from(value: MemberType) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+/// A membership product that a member has bought, parsed from text like
+/// "Standard Membership - £5.00".
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Subscription {
+    /// The name of the product. This may be empty if SUMS only shows a price.
+    pub name: String,
+    pub price: Money,
+}
+
+#[derive(Debug, Error)]
+#[error("Failed to parse subscription {0:?}")]
+pub struct SubscriptionParseError(pub String);
+
+impl Subscription {
+    pub fn new(name: String, price: Money) -> Self {
+        Self { name, price }
+    }
+
+    /// Parses the contents of the subscription column in SUMS's member list.
+    /// Returns `None` if the cell is blank, which happens for members who
+    /// haven't paid for anything.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the price can't be parsed.
+    pub fn parse(text: &str) -> Result<Option<Self>, SubscriptionParseError> {
+        let text = text.trim();
+
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        if let Ok(price) = text.parse() {
+            return Ok(Some(Self::new(String::new(), price)));
+        }
+
+        let (name, price) = text
+            .rsplit_once(" - ")
+            .ok_or_else(|| SubscriptionParseError(text.to_string()))?;
+        let price = price
+            .parse()
+            .map_err(|_| SubscriptionParseError(text.to_string()))?;
+
+        Ok(Some(Self::new(name.trim().to_string(), price)))
+    }
+
+    /// Which tier of membership this is, going by its name.
+    pub fn tier(&self) -> SubscriptionTier {
+        SubscriptionTier::from(self.name.as_str())
+    }
+}
+
+/// The tier of membership a [`Subscription`] is for, for grouping members
+/// by what they bought without matching on product names everywhere.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SubscriptionTier {
+    Standard,
+    Associate,
+    Alumni,
+    /// Any other product, with its name as it appears in SUMS.
+    Other(String),
+}
+
+impl From<&str> for SubscriptionTier {
+    /// Works out the tier from a product name like "Standard Membership" or
+    /// "HackSoc Associate Membership 2023/24", by looking for the tier's name
+    /// as a word in it, ignoring case. Names that don't mention a known tier
+    /// (or mention more than one) are [`SubscriptionTier::Other`].
+    fn from(name: &str) -> Self {
+        let mut tiers = name
+            .split(|c: char| !c.is_alphanumeric())
+            .filter_map(|word| match word.to_lowercase().as_str() {
+                "standard" | "full" => Some(Self::Standard),
+                "associate" => Some(Self::Associate),
+                "alumni" | "alumnus" | "alumna" => Some(Self::Alumni),
+                _ => None,
+            });
+
+        match (tiers.next(), tiers.next()) {
+            (Some(tier), None) => tier,
+            _ => Self::Other(name.trim().to_string()),
+        }
+    }
 }
 
-// impl<S> TryFrom<S> for MemberType
-// where
-//     S: AsRef<str>,
-// {
-//     fn try_from(value: S) -> Result<Self, Self::Error> {
-//         match value {
-//             "Student" => Ok(Self::Student),
-//             _ => Err(()),
-//         }
-//     }
-// }
+impl fmt::Display for SubscriptionTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Standard => f.write_str("Standard"),
+            Self::Associate => f.write_str("Associate"),
+            Self::Alumni => f.write_str("Alumni"),
+            Self::Other(name) => f.write_str(name),
+        }
+    }
+}
+
+impl fmt::Display for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.name.is_empty() {
+            write!(f, "{}", self.price)
+        } else {
+            write!(f, "{} - {}", self.name, self.price)
+        }
+    }
+}
 
+/// A member of a group, from a row of its members table.
+///
+/// Members are equal (and hash the same) if they have the same student ID,
+/// even if their other details differ, since the student ID is what
+/// identifies them. So a `HashSet<Member>` holds each person once, even if
+/// their subscription changed between scrapes. To find out whether anything
+/// else changed, compare the fields or use [`diff_members`](crate::diff::diff_members).
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Member {
     pub student_id: StudentId,
     pub name: String,
     pub member_type: MemberType,
-    pub subscription_purchased: String,
+    /// The membership the member bought, or `None` if they haven't bought one.
+    pub subscription_purchased: Option<Subscription>,
     pub date_joined: NaiveDate,
+    /// The member's email address, if the members table has an email column.
+    pub email: Option<String>,
+    /// When the member's subscription runs out, if the members table has an
+    /// expiry column.
+    pub subscription_expiry: Option<NaiveDate>,
+    /// The member's profile photo, if the members table shows one for them.
+    pub avatar_url: Option<Url>,
+}
+
+impl PartialEq for Member {
+    fn eq(&self, other: &Self) -> bool {
+        self.student_id == other.student_id
+    }
+}
+
+impl Eq for Member {}
+
+impl Hash for Member {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.student_id.hash(state);
+    }
 }
 
 impl Member {
@@ -33,7 +331,7 @@ impl Member {
         student_id: StudentId,
         name: String,
         member_type: MemberType,
-        subscription_purchased: String,
+        subscription_purchased: Option<Subscription>,
         date_joined: NaiveDate,
     ) -> Self {
         Self {
@@ -42,6 +340,856 @@ impl Member {
             member_type,
             subscription_purchased,
             date_joined,
+            email: None,
+            subscription_expiry: None,
+            avatar_url: None,
+        }
+    }
+
+    /// Parses one record of a members CSV, like the one from
+    /// [`SumsClient::export_members_csv`](crate::client::SumsClient::export_members_csv).
+    /// Columns are found by name from the CSV's `headers`, the same way as
+    /// the members table's headers, so they can be in any order.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if a required column is missing, or
+    /// if a field can't be parsed.
+    pub fn from_csv_record(
+        headers: &StringRecord,
+        record: &StringRecord,
+    ) -> Result<Self, SumsClientMembersError> {
+        let headers: Vec<String> = headers.iter().map(String::from).collect();
+
+        table::parse_csv_record(record, &MemberColumns::from_headers(&headers)?)
+    }
+
+    /// The member's details as field name to value pairs, for exporting to
+    /// formats that aren't built in, like rows of a database table. The keys
+    /// match the header of [`to_csv`](crate::export::to_csv), plus `email`,
+    /// `subscription_expiry` and `avatar_url`. Every key is always there, and
+    /// details the member doesn't have are blank. Dates are `YYYY-MM-DD`.
+    pub fn to_record(&self) -> BTreeMap<&'static str, String> {
+        let date = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+
+        BTreeMap::from([
+            ("student_id", self.student_id.to_string()),
+            ("name", self.name.clone()),
+            ("member_type", self.member_type.to_string()),
+            (
+                "subscription_purchased",
+                self.subscription_purchased
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            ),
+            ("date_joined", date(self.date_joined)),
+            ("email", self.email.clone().unwrap_or_default()),
+            (
+                "subscription_expiry",
+                self.subscription_expiry.map(date).unwrap_or_default(),
+            ),
+            (
+                "avatar_url",
+                self.avatar_url
+                    .as_ref()
+                    .map(ToString::to_string)
+                    .unwrap_or_default(),
+            ),
+        ])
+    }
+
+    /// Compares members by when they joined, oldest first, then by student
+    /// ID for members who joined on the same day. Use it to list members in
+    /// the order they joined, with `members.sort_by(Member::by_date_joined)`.
+    ///
+    /// This isn't `Member`'s [`Ord`], since members are equal whenever their
+    /// student IDs are, and an order by date wouldn't agree with that.
+    pub fn by_date_joined(a: &Member, b: &Member) -> Ordering {
+        a.date_joined
+            .cmp(&b.date_joined)
+            .then_with(|| a.student_id.cmp(&b.student_id))
+    }
+
+    /// Whether the member has a subscription that's valid on `today`.
+    ///
+    /// Members who never bought a subscription are inactive. If SUMS doesn't
+    /// show when subscriptions expire, anyone who bought one counts as
+    /// active, since SUMS only lists the current academic year's purchases.
+    pub fn is_subscription_active(&self, today: NaiveDate) -> bool {
+        match (&self.subscription_purchased, self.subscription_expiry) {
+            (None, _) => false,
+            (Some(_), Some(expiry)) => today <= expiry,
+            (Some(_), None) => true,
+        }
+    }
+
+    /// The member's first name. See [`split_name`] for how names are split.
+    pub fn first_name(&self) -> &str {
+        split_name(&self.name).0
+    }
+
+    /// The member's last name, which is empty if SUMS only has one name for
+    /// them. See [`split_name`] for how names are split.
+    pub fn last_name(&self) -> &str {
+        split_name(&self.name).1
+    }
+
+    /// Whether the member joined between `start` and `end`, inclusive.
+    pub fn joined_between(&self, start: NaiveDate, end: NaiveDate) -> bool {
+        (start..=end).contains(&self.date_joined)
+    }
+
+    /// Creates a [`MemberBuilder`], for making members in tests or from
+    /// synthetic data without spelling out every detail.
+    pub fn builder(student_id: StudentId) -> MemberBuilder {
+        MemberBuilder::new(student_id)
+    }
+}
+
+/// Builds a [`Member`] from just the details you care about. Anything not set
+/// is left with a default: an empty name, [`MemberType::Student`], no
+/// subscription, joining today, and no email, expiry or avatar.
+///
+/// ```
+/// use libsums::member::{Member, MemberType, StudentId};
+///
+/// let member = Member::builder(StudentId::try_from("20123456").unwrap())
+///     .name("Jane Doe")
+///     .member_type(MemberType::Committee)
+///     .build();
+///
+/// assert_eq!(member.name, "Jane Doe");
+/// assert!(member.subscription_purchased.is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MemberBuilder {
+    member: Member,
+}
+
+impl MemberBuilder {
+    pub fn new(student_id: StudentId) -> Self {
+        Self {
+            member: Member::new(
+                student_id,
+                String::new(),
+                MemberType::Student,
+                None,
+                Local::now().date_naive(),
+            ),
+        }
+    }
+
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.member.name = name.into();
+        self
+    }
+
+    pub fn member_type(mut self, member_type: MemberType) -> Self {
+        self.member.member_type = member_type;
+        self
+    }
+
+    pub fn subscription_purchased(mut self, subscription: Subscription) -> Self {
+        self.member.subscription_purchased = Some(subscription);
+        self
+    }
+
+    pub fn date_joined(mut self, date_joined: NaiveDate) -> Self {
+        self.member.date_joined = date_joined;
+        self
+    }
+
+    pub fn email<S: Into<String>>(mut self, email: S) -> Self {
+        self.member.email = Some(email.into());
+        self
+    }
+
+    pub fn subscription_expiry(mut self, subscription_expiry: NaiveDate) -> Self {
+        self.member.subscription_expiry = Some(subscription_expiry);
+        self
+    }
+
+    pub fn avatar_url(mut self, avatar_url: Url) -> Self {
+        self.member.avatar_url = Some(avatar_url);
+        self
+    }
+
+    pub fn build(self) -> Member {
+        self.member
+    }
+}
+
+/// Removes members with the same student ID as an earlier member, keeping the
+/// first of each. [`SumsClient::members`](crate::client::SumsClient::members)
+/// already does this, so this is for lists of members put together some
+/// other way, like from several scrapes.
+pub fn dedup_members(members: &mut Vec<Member>) {
+    let mut seen = HashSet::new();
+    members.retain(|member| seen.insert(member.student_id.clone()));
+}
+
+/// Counts how many members there are of each [`MemberType`]. Types nobody
+/// has are left out, so an empty list gives an empty map.
+pub fn count_by_type(members: &[Member]) -> HashMap<MemberType, usize> {
+    let mut counts = HashMap::new();
+
+    for member in members {
+        *counts.entry(member.member_type).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// The members who joined on or after `since`, in the order they're given.
+/// This is for when you already have every member, like from
+/// [`SumsClient::members`](crate::client::SumsClient::members);
+/// [`SumsClient::members_joined_between`](crate::client::SumsClient::members_joined_between)
+/// gets them straight from SUMS.
+pub fn members_since(members: &[Member], since: NaiveDate) -> Vec<&Member> {
+    members
+        .iter()
+        .filter(|member| member.date_joined >= since)
+        .collect()
+}
+
+/// Pairs each member with their committee role, matching them up by student
+/// ID. Members who aren't on the committee get `None`, and anyone with more
+/// than one role gets them all, joined with ", " in the committee's order.
+/// Committee members without a student ID can't be matched, so are ignored.
+pub fn with_committee_roles(
+    members: Vec<Member>,
+    committee: &[CommitteeMember],
+) -> Vec<(Member, Option<String>)> {
+    let mut roles: HashMap<&StudentId, Vec<&str>> = HashMap::new();
+
+    for committee_member in committee {
+        if let Some(student_id) = &committee_member.student_id {
+            roles
+                .entry(student_id)
+                .or_default()
+                .push(&committee_member.role);
         }
     }
+
+    members
+        .into_iter()
+        .map(|member| {
+            let role = roles.get(&member.student_id).map(|roles| roles.join(", "));
+            (member, role)
+        })
+        .collect()
+}
+
+/// Lowercase words that start a surname, like the "van der" in "Jane van der
+/// Berg".
+const SURNAME_PARTICLES: [&str; 14] = [
+    "al", "bin", "da", "de", "del", "della", "der", "di", "dos", "du", "la", "le", "van", "von",
+];
+
+/// Splits a full name into a first and last name. The last name normally
+/// starts at the last word, but starts earlier if there's a surname particle
+/// like "van" or "de" before it. Middle names stay with the first name, so
+/// "Mary Jane Smith" is "Mary Jane" and "Smith". Single word names have an
+/// empty last name.
+pub fn split_name(name: &str) -> (&str, &str) {
+    let name = name.trim();
+
+    // Where each word after the first starts, since the first word is always
+    // part of the first name
+    let word_starts: Vec<usize> = name
+        .char_indices()
+        .filter(|&(i, c)| i > 0 && !c.is_whitespace() && name[..i].ends_with(char::is_whitespace))
+        .map(|(i, _)| i)
+        .collect();
+
+    let Some(&last_word_start) = word_starts.last() else {
+        return (name, "");
+    };
+
+    let last_start = word_starts
+        .iter()
+        .copied()
+        .find(|&start| {
+            let word = name[start..].split_whitespace().next().unwrap_or_default();
+            SURNAME_PARTICLES.contains(&word)
+        })
+        .unwrap_or(last_word_start);
+
+    (name[..last_start].trim_end(), &name[last_start..])
+}
+
+/// A column that members can be sorted by, for
+/// [`SumsClient::members_sorted`](crate::client::SumsClient::members_sorted).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberSort {
+    ByName,
+    ByDateJoined,
+    ByStudentId,
+}
+
+impl MemberSort {
+    /// Sorts members in place by this column. The sort is stable, so members
+    /// with the same value keep their order.
+    pub fn sort(&self, members: &mut [Member], ascending: bool) {
+        members.sort_by(|a, b| {
+            let ordering = match self {
+                Self::ByName => a.name.cmp(&b.name),
+                Self::ByDateJoined => a.date_joined.cmp(&b.date_joined),
+                Self::ByStudentId => a.student_id.cmp(&b.student_id),
+            };
+
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+}
+
+/// Which of a member's details to read from the members table, for
+/// [`SumsClient::members_with_fields`](crate::client::SumsClient::members_with_fields).
+///
+/// The student ID is always read, since it's what identifies a member.
+/// Reading any other column costs one WebDriver round trip per member, and
+/// `avatar_url` costs two, since the image has to be found before its source
+/// can be read. Parsing is quick next to that, so dates and subscriptions
+/// aren't any more expensive than names.
+///
+/// Details that aren't read are left empty: a blank name, [`MemberType::Student`],
+/// no subscription, a join date of 1970-01-01 and `None` for the rest.
+///
+/// ```
+/// use libsums::member::MemberFields;
+///
+/// let names_only = MemberFields {
+///     name: true,
+///     ..MemberFields::NONE
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MemberFields {
+    pub name: bool,
+    pub member_type: bool,
+    pub subscription_purchased: bool,
+    pub date_joined: bool,
+    pub email: bool,
+    pub subscription_expiry: bool,
+    pub avatar_url: bool,
+}
+
+impl MemberFields {
+    /// Every detail, which is what [`SumsClient::members`](crate::client::SumsClient::members)
+    /// reads.
+    pub const ALL: MemberFields = MemberFields {
+        name: true,
+        member_type: true,
+        subscription_purchased: true,
+        date_joined: true,
+        email: true,
+        subscription_expiry: true,
+        avatar_url: true,
+    };
+
+    /// Nothing but the student ID.
+    pub const NONE: MemberFields = MemberFields {
+        name: false,
+        member_type: false,
+        subscription_purchased: false,
+        date_joined: false,
+        email: false,
+        subscription_expiry: false,
+        avatar_url: false,
+    };
+}
+
+impl Default for MemberFields {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Someone holding a position on a group's committee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommitteeMember {
+    pub name: String,
+    /// The position they hold, like "President" or "Treasurer".
+    pub role: String,
+    /// Their student ID, if SUMS shows one. Committee members who aren't
+    /// students (like staff contacts) won't have one.
+    pub student_id: Option<StudentId>,
+}
+
+impl CommitteeMember {
+    pub fn new(name: String, role: String, student_id: Option<StudentId>) -> Self {
+        Self {
+            name,
+            role,
+            student_id,
+        }
+    }
+}
+
+impl fmt::Display for Member {
+    /// Formats the member as a one line summary, like
+    /// `#12345678 Jane Doe (Student, joined 2023-10-01)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} ({}, joined {})",
+            self.student_id,
+            self.name,
+            self.member_type,
+            self.date_joined.format("%Y-%m-%d")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, str::FromStr};
+
+    use chrono::{Datelike, Local};
+
+    use super::{
+        count_by_type, dedup_members, members_since, split_name, with_committee_roles,
+        CommitteeMember, Member, MemberSort, MemberType, Money, NaiveDate, StudentId, Subscription,
+        SubscriptionTier,
+    };
+
+    #[test]
+    fn test_member_type_try_from() {
+        assert_eq!(
+            MemberType::try_from("Student").unwrap(),
+            MemberType::Student
+        );
+        assert_eq!(
+            MemberType::try_from(" Life Member ").unwrap(),
+            MemberType::LifeMember
+        );
+        assert!(MemberType::try_from("Honorary").is_err());
+    }
+
+    #[test]
+    fn test_member_type_round_trip() {
+        for member_type in MemberType::ALL {
+            assert_eq!(MemberType::from_str(member_type.as_str()), Ok(member_type));
+        }
+    }
+
+    #[test]
+    fn test_parse_student_id() {
+        let student_id = StudentId::try_from("01234567").unwrap();
+        assert_eq!(student_id.as_str(), "01234567");
+
+        assert!(StudentId::try_from("1234567").is_err());
+        assert!(StudentId::try_from("123456789").is_err());
+        assert!(StudentId::try_from("1234567a").is_err());
+        assert!(StudentId::try_from("").is_err());
+    }
+
+    #[test]
+    fn test_display_member() {
+        let member = Member::new(
+            StudentId::try_from("12345678").unwrap(),
+            "Jane Doe".to_string(),
+            MemberType::Student,
+            None,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(),
+        );
+
+        assert_eq!(
+            member.to_string(),
+            "#12345678 Jane Doe (Student, joined 2023-10-01)"
+        );
+        assert_eq!(MemberType::LifeMember.to_string(), "Life Member");
+    }
+
+    #[test]
+    fn test_joined_between() {
+        let member = Member::new(
+            StudentId::try_from("12345678").unwrap(),
+            "Jane Doe".to_string(),
+            MemberType::Student,
+            None,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(),
+        );
+
+        let day_before = NaiveDate::from_ymd_opt(2023, 9, 30).unwrap();
+        let day_of = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+        let day_after = NaiveDate::from_ymd_opt(2023, 10, 2).unwrap();
+
+        assert!(member.joined_between(day_of, day_after));
+        assert!(member.joined_between(day_before, day_of));
+        assert!(member.joined_between(day_of, day_of));
+        assert!(!member.joined_between(day_after, day_after));
+        assert!(!member.joined_between(day_before, day_before));
+    }
+
+    #[test]
+    fn test_member_builder() {
+        let date_joined = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+
+        let member = Member::builder(StudentId::try_from("12345678").unwrap())
+            .name("Jane Doe")
+            .date_joined(date_joined)
+            .email("jane.doe@nottingham.ac.uk")
+            .build();
+
+        assert_eq!(member.student_id, "12345678");
+        assert_eq!(member.name, "Jane Doe");
+        assert_eq!(member.member_type, MemberType::Student);
+        assert!(member.subscription_purchased.is_none());
+        assert_eq!(member.date_joined, date_joined);
+        assert_eq!(member.email.as_deref(), Some("jane.doe@nottingham.ac.uk"));
+        assert!(member.subscription_expiry.is_none());
+        assert!(member.avatar_url.is_none());
+    }
+
+    #[test]
+    fn test_member_builder_defaults() {
+        let before = Local::now().date_naive();
+        let member = Member::builder(StudentId::try_from("12345678").unwrap()).build();
+        let after = Local::now().date_naive();
+
+        assert!(member.name.is_empty());
+        assert_eq!(member.member_type, MemberType::Student);
+        assert!(member.subscription_purchased.is_none());
+        assert!((before..=after).contains(&member.date_joined));
+    }
+
+    /// Members who joined on the given days of October 2023, named after the
+    /// order they're given in.
+    fn members_joined_on(days: &[u32]) -> Vec<Member> {
+        days.iter()
+            .enumerate()
+            .map(|(i, day)| {
+                Member::new(
+                    StudentId::try_from(format!("2000000{}", i)).unwrap(),
+                    format!("Member {}", i),
+                    MemberType::Student,
+                    None,
+                    NaiveDate::from_ymd_opt(2023, 10, *day).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn days_joined(members: &[Member]) -> Vec<u32> {
+        members
+            .iter()
+            .map(|member| member.date_joined.day())
+            .collect()
+    }
+
+    #[test]
+    fn test_sort_by_date_joined_ascending() {
+        let mut members = members_joined_on(&[3, 1, 2]);
+        MemberSort::ByDateJoined.sort(&mut members, true);
+
+        assert_eq!(days_joined(&members), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_sort_by_date_joined_descending() {
+        let mut members = members_joined_on(&[3, 1, 2]);
+        MemberSort::ByDateJoined.sort(&mut members, false);
+
+        assert_eq!(days_joined(&members), [3, 2, 1]);
+    }
+
+    #[test]
+    fn test_sort_by_name() {
+        let mut members = members_joined_on(&[3, 1, 2]);
+        MemberSort::ByName.sort(&mut members, false);
+
+        assert_eq!(days_joined(&members), [2, 1, 3]);
+    }
+
+    #[test]
+    fn test_is_subscription_active() {
+        let today = NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+        let mut member = Member::new(
+            StudentId::try_from("12345678").unwrap(),
+            "Jane Doe".to_string(),
+            MemberType::Student,
+            None,
+            NaiveDate::from_ymd_opt(2023, 9, 1).unwrap(),
+        );
+
+        // Never purchased
+        assert!(!member.is_subscription_active(today));
+
+        member.subscription_purchased = Some(Subscription::new(
+            "Standard".to_string(),
+            Money::from_pennies(500),
+        ));
+        assert!(member.is_subscription_active(today));
+
+        member.subscription_expiry = Some(today);
+        assert!(member.is_subscription_active(today));
+
+        member.subscription_expiry = NaiveDate::from_ymd_opt(2023, 9, 30);
+        assert!(!member.is_subscription_active(today));
+    }
+
+    #[test]
+    fn test_split_name() {
+        assert_eq!(split_name("Jane Doe"), ("Jane", "Doe"));
+        assert_eq!(split_name("Jane"), ("Jane", ""));
+        assert_eq!(split_name("Jane van der Berg"), ("Jane", "van der Berg"));
+        assert_eq!(split_name("Mary Jane Smith"), ("Mary Jane", "Smith"));
+        assert_eq!(split_name("  Jane   Doe  "), ("Jane", "Doe"));
+        assert_eq!(split_name("Devon Smith"), ("Devon", "Smith"));
+    }
+
+    #[test]
+    fn test_member_first_and_last_name() {
+        let member = Member::new(
+            StudentId::try_from("12345678").unwrap(),
+            "Jane van der Berg".to_string(),
+            MemberType::Student,
+            None,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(),
+        );
+
+        assert_eq!(member.first_name(), "Jane");
+        assert_eq!(member.last_name(), "van der Berg");
+        assert_eq!(member.name, "Jane van der Berg");
+    }
+
+    #[test]
+    fn test_member_identity() {
+        let member = members_joined_on(&[1]).remove(0);
+        let mut updated = member.clone();
+        updated.subscription_purchased = Some(Subscription::new(
+            "Standard Membership".to_string(),
+            Money::from_pennies(500),
+        ));
+
+        assert_eq!(member, updated);
+
+        let members: HashSet<Member> = [member, updated].into_iter().collect();
+        assert_eq!(members.len(), 1);
+
+        let others = members_joined_on(&[1, 1]);
+        assert_ne!(others[0], others[1]);
+    }
+
+    #[test]
+    fn test_dedup_members() {
+        let mut members = members_joined_on(&[1, 2, 3]);
+        let mut duplicate = members[0].clone();
+        duplicate.name = "Someone Else".to_string();
+        members.push(duplicate);
+
+        dedup_members(&mut members);
+
+        assert_eq!(members.len(), 3);
+        assert_eq!(members[0].name, "Member 0");
+    }
+
+    #[test]
+    fn test_to_record() {
+        let mut member = Member::new(
+            StudentId::try_from("20123456").unwrap(),
+            "Jane Doe".to_string(),
+            MemberType::Student,
+            Some(Subscription::new(
+                "Standard Membership".to_string(),
+                Money::from_pennies(500),
+            )),
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(),
+        );
+        member.email = Some("jane.doe@nottingham.ac.uk".to_string());
+
+        let record = member.to_record();
+
+        assert_eq!(record["student_id"], "20123456");
+        assert_eq!(record["name"], "Jane Doe");
+        assert_eq!(record["member_type"], "Student");
+        assert_eq!(
+            record["subscription_purchased"],
+            "Standard Membership - £5.00"
+        );
+        assert_eq!(record["date_joined"], "2023-10-01");
+        assert_eq!(record["email"], "jane.doe@nottingham.ac.uk");
+        assert_eq!(record["subscription_expiry"], "");
+        assert_eq!(record["avatar_url"], "");
+    }
+
+    #[test]
+    fn test_sort_by_date_joined() {
+        let mut members = members_joined_on(&[3, 1, 2, 1]);
+
+        members.sort_by(Member::by_date_joined);
+
+        assert_eq!(days_joined(&members), [1, 1, 2, 3]);
+        // Same day, so the student ID decides
+        assert_eq!(members[0].student_id, "20000001");
+        assert_eq!(members[1].student_id, "20000003");
+    }
+
+    #[test]
+    fn test_count_by_type() {
+        let mut members = members_joined_on(&[1, 2, 3, 4]);
+        members[1].member_type = MemberType::Associate;
+        members[3].member_type = MemberType::LifeMember;
+
+        let counts = count_by_type(&members);
+
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&MemberType::Student], 2);
+        assert_eq!(counts[&MemberType::Associate], 1);
+        assert_eq!(counts[&MemberType::LifeMember], 1);
+        assert!(!counts.contains_key(&MemberType::Staff));
+
+        assert!(count_by_type(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_members_since() {
+        let members = members_joined_on(&[3, 1, 4, 2]);
+
+        let since = members_since(&members, NaiveDate::from_ymd_opt(2023, 10, 3).unwrap());
+
+        assert_eq!(since, [&members[0], &members[2]]);
+        assert!(members_since(&members, NaiveDate::from_ymd_opt(2023, 10, 5).unwrap()).is_empty());
+        assert!(members_since(&[], NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_with_committee_roles() {
+        let members = members_joined_on(&[1, 2, 3]);
+        let committee = [
+            CommitteeMember::new(
+                "Member 0".to_string(),
+                "President".to_string(),
+                Some(members[0].student_id.clone()),
+            ),
+            CommitteeMember::new(
+                "Member 2".to_string(),
+                "Treasurer".to_string(),
+                Some(members[2].student_id.clone()),
+            ),
+            CommitteeMember::new(
+                "Member 2".to_string(),
+                "Welfare Officer".to_string(),
+                Some(members[2].student_id.clone()),
+            ),
+            CommitteeMember::new("Staff Contact".to_string(), "Advisor".to_string(), None),
+        ];
+
+        let roles: Vec<_> = with_committee_roles(members, &committee)
+            .into_iter()
+            .map(|(_, role)| role)
+            .collect();
+
+        assert_eq!(
+            roles,
+            [
+                Some("President".to_string()),
+                None,
+                Some("Treasurer, Welfare Officer".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_subscription() {
+        assert_eq!(
+            Subscription::parse("Standard Membership - £5.00").unwrap(),
+            Some(Subscription::new(
+                "Standard Membership".to_string(),
+                Money::from_pennies(500)
+            ))
+        );
+        assert_eq!(
+            Subscription::parse("£5.00").unwrap(),
+            Some(Subscription::new(String::new(), Money::from_pennies(500)))
+        );
+        assert_eq!(
+            Subscription::parse("Lifetime - Membership - £1,250.5").unwrap(),
+            Some(Subscription::new(
+                "Lifetime - Membership".to_string(),
+                Money::from_pennies(125050)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_subscription_tier() {
+        let tier = |name: &str| Subscription::new(name.to_string(), Money::ZERO).tier();
+
+        assert_eq!(tier("Standard Membership"), SubscriptionTier::Standard);
+        assert_eq!(tier("Full Member"), SubscriptionTier::Standard);
+        assert_eq!(
+            tier("HackSoc Associate Membership 2023/24"),
+            SubscriptionTier::Associate
+        );
+        assert_eq!(tier("ALUMNI"), SubscriptionTier::Alumni);
+        assert_eq!(
+            tier("Hoodie Bundle"),
+            SubscriptionTier::Other("Hoodie Bundle".to_string())
+        );
+        assert_eq!(tier(""), SubscriptionTier::Other(String::new()));
+
+        // Ambiguous names aren't guessed at
+        assert_eq!(
+            tier("Standard to Associate Upgrade"),
+            SubscriptionTier::Other("Standard to Associate Upgrade".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_free_subscription() {
+        assert_eq!(
+            Subscription::parse("Free").unwrap(),
+            Some(Subscription::new(String::new(), Money::from_pennies(0)))
+        );
+        assert_eq!(
+            Subscription::parse("Associate Membership - £0.00").unwrap(),
+            Some(Subscription::new(
+                "Associate Membership".to_string(),
+                Money::from_pennies(0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_subscription() {
+        assert_eq!(Subscription::parse("").unwrap(), None);
+        assert_eq!(Subscription::parse("  ").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_invalid_subscription() {
+        assert!(Subscription::parse("Standard Membership").is_err());
+        assert!(Subscription::parse("Standard Membership - £5.000").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_member_serde_round_trip() {
+        let member = Member::new(
+            StudentId::try_from("20123456").unwrap(),
+            "Jane Doe".to_string(),
+            MemberType::LifeMember,
+            Some(Subscription::new(
+                "Standard Membership".to_string(),
+                Money::from_pennies(500),
+            )),
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(),
+        );
+
+        let json = serde_json::to_string(&member).unwrap();
+        assert!(json.contains(r#""member_type":"Life Member""#));
+        assert!(json.contains(r#""date_joined":"2023-10-01""#));
+
+        // Members are only compared by student ID, so compare everything
+        let deserialized: Member = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{:?}", member), format!("{:?}", deserialized));
+    }
 }
@@ -0,0 +1,266 @@
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// An amount of money in pounds and pence, like the price of a subscription
+/// or product. This is negative for refunds.
+///
+/// With the `serde` feature this is (de)serialized as a number of pennies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(transparent))]
+pub struct Money {
+    pub pennies: i64,
+}
+
+impl Money {
+    /// No money at all, which is what SUMS shows as "Free".
+    pub const ZERO: Money = Money::from_pennies(0);
+
+    pub const fn from_pennies(pennies: i64) -> Self {
+        Self { pennies }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.pennies == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.pennies < 0
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Failed to parse price {0:?}")]
+pub struct MoneyParseError(pub String);
+
+impl FromStr for Money {
+    type Err = MoneyParseError;
+
+    /// Parses a price as SUMS shows it, like "£5.00", "£1,000", "5", "Free"
+    /// or "-£5.00", ignoring surrounding whitespace. A single digit after the
+    /// decimal point is tens of pence, so "£5.5" is £5.50.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || MoneyParseError(text.to_string());
+        let trimmed = text.trim();
+
+        if trimmed.eq_ignore_ascii_case("free") {
+            return Ok(Self::ZERO);
+        }
+
+        // The minus sign can go either side of the currency symbol
+        let (negative, amount) = match trimmed.strip_prefix('-') {
+            Some(amount) => (true, amount.trim_start_matches(['£', '$', '€'])),
+            None => {
+                let amount = trimmed.trim_start_matches(['£', '$', '€']);
+
+                match amount.strip_prefix('-') {
+                    Some(amount) => (true, amount),
+                    None => (false, amount),
+                }
+            }
+        };
+
+        let amount = amount.trim().replace(',', "");
+        let (pounds, pence) = amount.split_once('.').unwrap_or((&amount, "0"));
+
+        if pounds.is_empty()
+            || pence.is_empty()
+            || pence.len() > 2
+            || !pounds
+                .chars()
+                .chain(pence.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(error());
+        }
+
+        // "£5.5" means 50p, not 5p
+        let pence = if pence.len() == 1 {
+            pence.parse::<i64>().map_err(|_| error())? * 10
+        } else {
+            pence.parse::<i64>().map_err(|_| error())?
+        };
+
+        let pennies = pounds
+            .parse::<i64>()
+            .ok()
+            .and_then(|pounds| pounds.checked_mul(100))
+            .and_then(|pennies| pennies.checked_add(pence))
+            .ok_or_else(error)?;
+
+        Ok(Self::from_pennies(if negative {
+            -pennies
+        } else {
+            pennies
+        }))
+    }
+}
+
+impl fmt::Display for Money {
+    /// Formats the amount like "£5.00", or "-£5.00" if it's negative.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let pennies = self.pennies.unsigned_abs();
+
+        write!(f, "{}£{}.{:02}", sign, pennies / 100, pennies % 100)
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money::from_pennies(self.pennies + other.pennies)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money::from_pennies(self.pennies - other.pennies)
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money::from_pennies(-self.pennies)
+    }
+}
+
+impl Mul<i64> for Money {
+    type Output = Money;
+
+    fn mul(self, quantity: i64) -> Money {
+        Money::from_pennies(self.pennies * quantity)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.pennies += other.pennies;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.pennies -= other.pennies;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Money;
+
+    #[test]
+    fn test_parse_money() {
+        assert_eq!("£5.00".parse(), Ok(Money::from_pennies(500)));
+        assert_eq!("5".parse(), Ok(Money::from_pennies(500)));
+        assert_eq!(" £5.5 ".parse(), Ok(Money::from_pennies(550)));
+        assert_eq!("€0.99".parse(), Ok(Money::from_pennies(99)));
+        assert_eq!("£1,250.50".parse(), Ok(Money::from_pennies(125050)));
+    }
+
+    #[test]
+    fn test_parse_zero_money() {
+        assert_eq!("Free".parse(), Ok(Money::ZERO));
+        assert_eq!("FREE".parse(), Ok(Money::ZERO));
+        assert_eq!("£0.00".parse(), Ok(Money::ZERO));
+    }
+
+    #[test]
+    fn test_parse_negative_money() {
+        assert_eq!("-£5.00".parse(), Ok(Money::from_pennies(-500)));
+        assert_eq!("£-5.00".parse(), Ok(Money::from_pennies(-500)));
+        assert_eq!("-0.01".parse(), Ok(Money::from_pennies(-1)));
+    }
+
+    #[test]
+    fn test_parse_large_money() {
+        assert_eq!(
+            "£1,000,000,000.00".parse(),
+            Ok(Money::from_pennies(100_000_000_000))
+        );
+        assert!("£1000000000000000000000".parse::<Money>().is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_money() {
+        assert!("".parse::<Money>().is_err());
+        assert!("£".parse::<Money>().is_err());
+        assert!("twenty quid".parse::<Money>().is_err());
+        assert!("£5.000".parse::<Money>().is_err());
+        assert!("£5.".parse::<Money>().is_err());
+        assert!("--5".parse::<Money>().is_err());
+    }
+
+    #[test]
+    fn test_display_money() {
+        assert_eq!(Money::from_pennies(500).to_string(), "£5.00");
+        assert_eq!(Money::from_pennies(5).to_string(), "£0.05");
+        assert_eq!(Money::ZERO.to_string(), "£0.00");
+        assert_eq!(Money::from_pennies(-550).to_string(), "-£5.50");
+        assert_eq!(
+            Money::from_pennies(100_000_000_000).to_string(),
+            "£1000000000.00"
+        );
+        assert_eq!(
+            Money::from_pennies(i64::MIN).to_string(),
+            "-£92233720368547758.08"
+        );
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for pennies in [0, 1, 99, 500, -500, 123_456_789] {
+            let money = Money::from_pennies(pennies);
+
+            assert_eq!(money.to_string().parse(), Ok(money));
+        }
+    }
+
+    #[test]
+    fn test_money_arithmetic() {
+        let price = Money::from_pennies(500);
+        let refund = Money::from_pennies(-500);
+
+        assert_eq!(price + refund, Money::ZERO);
+        assert_eq!(price - Money::from_pennies(150), Money::from_pennies(350));
+        assert_eq!(-price, refund);
+        assert_eq!(price * 3, Money::from_pennies(1500));
+
+        let mut total = Money::ZERO;
+        total += price;
+        total += price;
+        total -= Money::from_pennies(1);
+        assert_eq!(total, Money::from_pennies(999));
+
+        assert_eq!(
+            [price, price, refund].iter().sum::<Money>(),
+            Money::from_pennies(500)
+        );
+        assert!(refund.is_negative());
+        assert!((price + refund).is_zero());
+    }
+}
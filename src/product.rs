@@ -0,0 +1,25 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::money::Money;
+
+/// A product from a group's products page on the student dashboard, such as a
+/// membership or a piece of merch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Product {
+    pub name: String,
+    pub price: Money,
+    /// Whether the product can currently be bought.
+    pub available: bool,
+}
+
+impl Product {
+    pub fn new(name: String, price: Money, available: bool) -> Self {
+        Self {
+            name,
+            price,
+            available,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::diff::diff_members;
+use crate::member::{count_by_type, Member, MemberType};
+
+/// A summary of one scrape of a group's members, from [`generate_report`].
+///
+/// With the `serde` feature this can be serialized to post somewhere, like a
+/// chat bot. The counts are keyed by each type's display name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ScrapeReport {
+    /// How many members the group has.
+    pub total_members: usize,
+    /// How many members there are of each type. Types with no members are
+    /// left out.
+    pub counts_by_type: HashMap<MemberType, usize>,
+    /// Members that weren't there in the previous scrape, in the order they
+    /// were scraped. This is empty if there wasn't a previous scrape.
+    pub newly_joined: Vec<Member>,
+}
+
+/// Summarises the `current` members of a group, and what's new since the
+/// `previous` scrape if there was one. Members are matched up by student ID,
+/// like [`diff_members`].
+pub fn generate_report(previous: Option<&[Member]>, current: &[Member]) -> ScrapeReport {
+    ScrapeReport {
+        total_members: current.len(),
+        counts_by_type: count_by_type(current),
+        newly_joined: previous
+            .map(|previous| diff_members(previous, current).added)
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::NaiveDate;
+
+    use crate::member::{Member, MemberType, StudentId};
+
+    use super::generate_report;
+    #[cfg(feature = "serde")]
+    use super::ScrapeReport;
+
+    fn member(student_id: &str, member_type: MemberType) -> Member {
+        Member::new(
+            StudentId::try_from(student_id).unwrap(),
+            "Jane Doe".to_string(),
+            member_type,
+            None,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(),
+        )
+    }
+
+    fn snapshots() -> (Vec<Member>, Vec<Member>) {
+        let previous = vec![
+            member("20000001", MemberType::Student),
+            member("20000002", MemberType::Committee),
+        ];
+        let current = vec![
+            member("20000001", MemberType::Student),
+            member("20000002", MemberType::Committee),
+            member("20000003", MemberType::Student),
+            member("20000004", MemberType::Associate),
+        ];
+
+        (previous, current)
+    }
+
+    #[test]
+    fn test_generate_report() {
+        let (previous, current) = snapshots();
+
+        let report = generate_report(Some(&previous), &current);
+
+        assert_eq!(report.total_members, 4);
+        assert_eq!(
+            report.counts_by_type,
+            HashMap::from([
+                (MemberType::Student, 2),
+                (MemberType::Committee, 1),
+                (MemberType::Associate, 1),
+            ])
+        );
+        assert_eq!(
+            report.newly_joined,
+            [
+                member("20000003", MemberType::Student),
+                member("20000004", MemberType::Associate),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_report_without_previous() {
+        let (_, current) = snapshots();
+
+        let report = generate_report(None, &current);
+
+        assert_eq!(report.total_members, 4);
+        assert!(report.newly_joined.is_empty());
+    }
+
+    #[test]
+    fn test_generate_report_empty() {
+        let report = generate_report(Some(&[]), &[]);
+
+        assert_eq!(report.total_members, 0);
+        assert!(report.counts_by_type.is_empty());
+        assert!(report.newly_joined.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_report_round_trip() {
+        let (previous, current) = snapshots();
+        let report = generate_report(Some(&previous), &current);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["total_members"], 4);
+        assert_eq!(json["counts_by_type"]["Student"], 2);
+        assert_eq!(json["newly_joined"].as_array().unwrap().len(), 2);
+
+        assert_eq!(
+            serde_json::from_value::<ScrapeReport>(json).unwrap(),
+            report
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use chrono::{DateTime, Duration, Utc};
+use cookie::{time::OffsetDateTime, Cookie, SameSite};
+
+/// A snapshot of a logged in browser session, from
+/// [`SumsClient::export_session`](crate::client::SumsClient::export_session).
+///
+/// With the `serde` feature this can be saved between runs, so a scheduled
+/// job doesn't have to go through UoN SSO every time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionState {
+    pub cookies: Vec<SessionCookie>,
+    pub exported_at: DateTime<Utc>,
+}
+
+impl SessionState {
+    pub fn new(cookies: Vec<SessionCookie>, exported_at: DateTime<Utc>) -> Self {
+        Self {
+            cookies,
+            exported_at,
+        }
+    }
+
+    /// Whether the session was exported more than `ttl` ago. SUMS doesn't say
+    /// how long its sessions last, so this is a cheap check to skip restoring
+    /// a session that's almost certainly gone stale.
+    pub fn is_older_than(&self, ttl: Duration) -> bool {
+        Utc::now() - self.exported_at > ttl
+    }
+}
+
+/// Who [`SumsClient::authenticate_detailed`](crate::client::SumsClient::authenticate_detailed)
+/// logged in as, for audit logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuthInfo {
+    /// The name shown in the SU website's user menu, or `None` if it couldn't
+    /// be found.
+    pub display_name: Option<String>,
+}
+
+impl AuthInfo {
+    pub fn new(display_name: Option<String>) -> Self {
+        Self { display_name }
+    }
+}
+
+/// One cookie from a [`SessionState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionCookie {
+    pub name: String,
+    pub value: String,
+    pub domain: Option<String>,
+    pub path: Option<String>,
+    pub secure: Option<bool>,
+    pub http_only: Option<bool>,
+    /// When the cookie expires, in seconds since the Unix epoch. `None` for
+    /// cookies that only last as long as the browser.
+    pub expires: Option<i64>,
+    pub same_site: Option<String>,
+}
+
+impl SessionCookie {
+    /// Whether the browser would send this cookie to `host`.
+    pub(crate) fn matches_host(&self, host: &str) -> bool {
+        match &self.domain {
+            Some(domain) => {
+                let domain = domain.trim_start_matches('.');
+
+                host == domain
+                    || (host.ends_with(domain) && host[..host.len() - domain.len()].ends_with('.'))
+            }
+            None => false,
+        }
+    }
+
+    /// Converts back to a cookie that can be added to the browser.
+    pub(crate) fn to_cookie(&self) -> Cookie<'static> {
+        let mut cookie = Cookie::new(self.name.clone(), self.value.clone());
+
+        if let Some(domain) = &self.domain {
+            cookie.set_domain(domain.clone());
+        }
+
+        if let Some(path) = &self.path {
+            cookie.set_path(path.clone());
+        }
+
+        if let Some(secure) = self.secure {
+            cookie.set_secure(secure);
+        }
+
+        if let Some(http_only) = self.http_only {
+            cookie.set_http_only(http_only);
+        }
+
+        if let Some(expires) = self.expires {
+            cookie.set_expires(OffsetDateTime::from_unix_timestamp(expires).ok());
+        }
+
+        if let Some(same_site) = &self.same_site {
+            let same_site = match same_site.as_str() {
+                "Strict" => Some(SameSite::Strict),
+                "Lax" => Some(SameSite::Lax),
+                "None" => Some(SameSite::None),
+                _ => None,
+            };
+
+            cookie.set_same_site(same_site);
+        }
+
+        cookie
+    }
+}
+
+impl From<&Cookie<'_>> for SessionCookie {
+    fn from(cookie: &Cookie<'_>) -> Self {
+        Self {
+            name: cookie.name().to_string(),
+            value: cookie.value().to_string(),
+            domain: cookie.domain().map(String::from),
+            path: cookie.path().map(String::from),
+            secure: cookie.secure(),
+            http_only: cookie.http_only(),
+            expires: cookie
+                .expires()
+                .and_then(|expires| expires.datetime())
+                .map(|expires| expires.unix_timestamp()),
+            same_site: cookie.same_site().map(|same_site| same_site.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use cookie::{Cookie, SameSite};
+
+    use super::{SessionCookie, SessionState};
+
+    fn session_cookie(domain: Option<&str>) -> SessionCookie {
+        SessionCookie {
+            name: "session".to_string(),
+            value: "abc123".to_string(),
+            domain: domain.map(String::from),
+            path: Some("/".to_string()),
+            secure: Some(true),
+            http_only: Some(true),
+            expires: Some(1_700_000_000),
+            same_site: Some("Lax".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_cookie_round_trip() {
+        let original = session_cookie(Some("su.nottingham.ac.uk"));
+        let cookie = original.to_cookie();
+
+        assert_eq!(cookie.same_site(), Some(SameSite::Lax));
+        assert_eq!(SessionCookie::from(&cookie), original);
+    }
+
+    #[test]
+    fn test_cookie_without_attributes() {
+        let cookie = Cookie::new("session", "abc123");
+        let session_cookie = SessionCookie::from(&cookie);
+
+        assert_eq!(session_cookie.domain, None);
+        assert_eq!(session_cookie.expires, None);
+        assert_eq!(session_cookie.same_site, None);
+    }
+
+    #[test]
+    fn test_matches_host() {
+        let exact = session_cookie(Some("su.nottingham.ac.uk"));
+        assert!(exact.matches_host("su.nottingham.ac.uk"));
+        assert!(!exact.matches_host("student-dashboard.sums.su"));
+
+        let parent = session_cookie(Some(".nottingham.ac.uk"));
+        assert!(parent.matches_host("su.nottingham.ac.uk"));
+        assert!(!parent.matches_host("notnottingham.ac.uk"));
+
+        assert!(!session_cookie(None).matches_host("su.nottingham.ac.uk"));
+    }
+
+    #[test]
+    fn test_is_older_than() {
+        let state = SessionState::new(Vec::new(), Utc::now() - Duration::hours(2));
+
+        assert!(state.is_older_than(Duration::hours(1)));
+        assert!(!state.is_older_than(Duration::hours(3)));
+    }
+}
@@ -0,0 +1,37 @@
+//! Logging macros that forward to `tracing` when the `tracing` feature is
+//! enabled, and compile to nothing otherwise. Only the `format!` style of
+//! arguments is supported, so that the arguments are still type checked (and
+//! count as used) when logging is disabled.
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn ignore(_: std::fmt::Arguments<'_>) {}
+
+macro_rules! log_event {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        ::tracing::$level!($($arg)+);
+
+        #[cfg(not(feature = "tracing"))]
+        if false {
+            $crate::trace::ignore(::std::format_args!($($arg)+));
+        }
+    }};
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        log_event!(debug, $($arg)+)
+    };
+}
+
+macro_rules! warn {
+    ($($arg:tt)+) => {
+        log_event!(warn, $($arg)+)
+    };
+}
+
+macro_rules! error {
+    ($($arg:tt)+) => {
+        log_event!(error, $($arg)+)
+    };
+}
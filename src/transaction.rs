@@ -0,0 +1,32 @@
+use chrono::NaiveDate;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::member::StudentId;
+use crate::money::Money;
+
+/// A sale from a group's sales report on the student dashboard, like someone
+/// buying a membership.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Transaction {
+    /// Who bought it, or `None` if the buyer wasn't a student, like a guest
+    /// buying from the SU shop.
+    pub member: Option<StudentId>,
+    /// The name of the product that was bought.
+    pub product: String,
+    /// How much was paid. This is negative for refunds.
+    pub amount: Money,
+    pub date: NaiveDate,
+}
+
+impl Transaction {
+    pub fn new(member: Option<StudentId>, product: String, amount: Money, date: NaiveDate) -> Self {
+        Self {
+            member,
+            product,
+            amount,
+            date,
+        }
+    }
+}